@@ -0,0 +1,551 @@
+//! Computer-controlled players.
+//!
+//! A [`Strategy`] observes the game and issues the same [`Command`]s
+//! that a human player issues through the key binding,
+//! one command per tick at most.
+//!
+//! Positions used by strategies are relative to the player's territory:
+//! `(row, depth)`, where depth `0` is the column next to the player's base
+//! and depth `n_columns - 1` is the column next to the [division line].
+//! This way, the same strategy works for both players.
+//!
+//! [`Strategy`]: trait.Strategy.html
+//! [`Command`]: ../controller/enum.Command.html
+//! [division line]: ../game/index.html#division-line
+
+use crate::controller::{Command, Direction};
+use crate::game::PlacementError;
+use crate::{object, Game, Player, Resource};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// A strategy for a computer-controlled player.
+pub trait Strategy: fmt::Debug {
+    /// Decides the command to issue in the current tick, if any.
+    ///
+    /// `selected_cell` is the cell currently selected by `player`.
+    fn decide(
+        &mut self,
+        game: &Game,
+        player: Player,
+        selected_cell: (usize, usize),
+    ) -> Option<Command>;
+}
+
+/// Converts a position relative to the player's territory
+/// into an absolute position.
+pub fn absolute_position(
+    game: &Game,
+    player: Player,
+    (row, depth): (usize, usize),
+) -> (usize, usize) {
+    let n_columns = game.settings().n_columns;
+    match player {
+        Player::Left => (row, depth),
+        Player::Right => (row, 2 * n_columns - 1 - depth),
+    }
+}
+
+/// Returns the direction to move the selection in
+/// to get from `from` closer to `to`,
+/// or `None` if `from` equals `to`.
+pub fn step_towards(from: (usize, usize), to: (usize, usize)) -> Option<Direction> {
+    use std::cmp::Ordering;
+
+    match (from.0.cmp(&to.0), from.1.cmp(&to.1)) {
+        (Ordering::Greater, _) => Some(Direction::Up),
+        (Ordering::Less, _) => Some(Direction::Down),
+        (_, Ordering::Greater) => Some(Direction::Left),
+        (_, Ordering::Less) => Some(Direction::Right),
+        (Ordering::Equal, Ordering::Equal) => None,
+    }
+}
+
+/// The next step towards placing an object.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Attempt {
+    /// Issue the command.
+    Command(Command),
+    /// The placement is possible later.
+    Wait,
+    /// The placement is impossible.
+    Failed,
+}
+
+/// Works towards placing the object with the specified index
+/// at the specified absolute position.
+///
+/// The selection is moved to the position first,
+/// and the object is placed once the placement is allowed.
+pub fn attempt_placement(
+    game: &Game,
+    player: Player,
+    selected_cell: (usize, usize),
+    position: (usize, usize),
+    index: usize,
+) -> Attempt {
+    if let Some(direction) = step_towards(selected_cell, position) {
+        return Attempt::Command(Command::Move(direction));
+    }
+
+    match game.check_placement(player, position, index) {
+        Ok(()) => Attempt::Command(Command::Place(index)),
+        Err(
+            PlacementError::OnCooldown { .. }
+            | PlacementError::InsufficientKeys { .. }
+            | PlacementError::InsufficientResources { .. },
+        ) => Attempt::Wait,
+        Err(_) => Attempt::Failed,
+    }
+}
+
+/// A strategy that never does anything.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Idle;
+
+impl Strategy for Idle {
+    fn decide(
+        &mut self,
+        _game: &Game,
+        _player: Player,
+        _selected_cell: (usize, usize),
+    ) -> Option<Command> {
+        None
+    }
+}
+
+/// A step in a build order.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BuildStep {
+    /// The placement index.
+    pub index: usize,
+
+    /// The position, relative to the player's territory.
+    pub position: (usize, usize),
+}
+
+/// A scripted strategy that follows a fixed build order.
+///
+/// Each step waits until the placement is allowed.
+/// Steps that can never succeed,
+/// for example because the cell is occupied, are skipped.
+#[derive(Clone, Debug)]
+pub struct BuildOrder {
+    steps: Vec<BuildStep>,
+    next: usize,
+}
+
+impl BuildOrder {
+    /// Creates a build order from the specified steps.
+    pub fn new(steps: Vec<BuildStep>) -> Self {
+        Self { steps, next: 0 }
+    }
+
+    /// Loads a build order from a RON file
+    /// containing a list of steps.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("cannot read build order {}", path.display()))?;
+        let steps = ron::from_str(&source).context("cannot parse build order")?;
+        Ok(Self::new(steps))
+    }
+}
+
+impl Strategy for BuildOrder {
+    fn decide(
+        &mut self,
+        game: &Game,
+        player: Player,
+        selected_cell: (usize, usize),
+    ) -> Option<Command> {
+        while let Some(step) = self.steps.get(self.next) {
+            let position = absolute_position(game, player, step.position);
+            match attempt_placement(game, player, selected_cell, position, step.index) {
+                Attempt::Command(command) => {
+                    if let Command::Place(_) = command {
+                        self.next += 1;
+                    }
+                    return Some(command);
+                }
+                Attempt::Wait => return None,
+                Attempt::Failed => self.next += 1,
+            }
+        }
+        None
+    }
+}
+
+/// A strategy that places random objects on random empty cells.
+#[derive(Clone, Debug)]
+pub struct Random {
+    rng: Rng,
+    target: Option<((usize, usize), usize)>,
+}
+
+impl Random {
+    /// Creates a random strategy with the specified seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: Rng::new(seed),
+            target: None,
+        }
+    }
+}
+
+impl Strategy for Random {
+    fn decide(
+        &mut self,
+        game: &Game,
+        player: Player,
+        selected_cell: (usize, usize),
+    ) -> Option<Command> {
+        let n_placements = game.players()[player].placements.len();
+        if n_placements == 0 {
+            return None;
+        }
+
+        let (position, index) = if let Some(target) = self.target {
+            target
+        } else {
+            let empty_cells: Vec<_> = game
+                .territory(player)
+                .flat_map(|column| (0..game.settings().n_rows).map(move |row| (row, column)))
+                .filter(|&position| game.cells()[position].borrow().object.is_none())
+                .collect();
+            if empty_cells.is_empty() {
+                return None;
+            }
+            let target = (
+                empty_cells[self.rng.below(empty_cells.len())],
+                self.rng.below(n_placements),
+            );
+            self.target = Some(target);
+            target
+        };
+
+        match attempt_placement(game, player, selected_cell, position, index) {
+            Attempt::Command(command) => {
+                if let Command::Place(_) = command {
+                    self.target = None;
+                }
+                Some(command)
+            }
+            Attempt::Wait => None,
+            Attempt::Failed => {
+                self.target = None;
+                None
+            }
+        }
+    }
+}
+
+/// A greedy strategy.
+///
+/// In order of priority, the greedy strategy:
+///
+/// 1. builds key objects near its base until it owns `n_key_objects` of them;
+/// 2. on each row where the enemy has a fire object,
+///    places a barrier at the front and a fire object behind it;
+/// 3. places fire objects on the remaining rows, bases first;
+/// 4. builds more key objects.
+///
+/// The first placement that is currently allowed is made.
+/// If no placement is allowed,
+/// the strategy waits for the placement with the highest priority.
+#[derive(Clone, Debug)]
+pub struct Greedy {
+    n_key_objects: usize,
+}
+
+impl Greedy {
+    /// Creates a greedy strategy that builds the specified number
+    /// of key objects before anything else.
+    pub fn new(n_key_objects: usize) -> Self {
+        Self { n_key_objects }
+    }
+
+    /// Chooses the next placement as `(position, index)`.
+    fn choose(&self, game: &Game, player: Player) -> Option<((usize, usize), usize)> {
+        let candidates = self.candidates(game, player);
+        candidates
+            .iter()
+            .copied()
+            .find(|&(position, index)| game.check_placement(player, position, index).is_ok())
+            .or_else(|| candidates.first().copied())
+    }
+
+    /// Returns the possible placements as `(position, index)`,
+    /// in order of priority.
+    fn candidates(&self, game: &Game, player: Player) -> Vec<((usize, usize), usize)> {
+        let key = find_placement(game, player, is_key);
+        let fire = find_placement(game, player, is_fire);
+        let barrier = find_placement(game, player, is_barrier);
+
+        let settings = game.settings();
+        let n_columns = settings.n_columns;
+        let rows = 0..settings.n_rows;
+
+        let count = |owner: Player, row: usize, predicate: fn(&object::Kind) -> bool| {
+            game.territory(owner)
+                .filter(|&column| {
+                    game.cells()[(row, column)]
+                        .borrow()
+                        .object
+                        .as_ref()
+                        .is_some_and(|object| {
+                            object.owner == owner && predicate(&object.object.kind)
+                        })
+                })
+                .count()
+        };
+
+        let empty = |row: usize, depth: usize| {
+            let position = absolute_position(game, player, (row, depth));
+            game.cells()[position].borrow().object.is_none()
+        };
+        // the empty cell in the row closest to the base
+        let back = |row: usize| {
+            (0..n_columns)
+                .find(|&depth| empty(row, depth))
+                .map(|depth| absolute_position(game, player, (row, depth)))
+        };
+        // the empty cell in the row closest to the division line
+        let front = |row: usize| {
+            (0..n_columns)
+                .rev()
+                .find(|&depth| empty(row, depth))
+                .map(|depth| absolute_position(game, player, (row, depth)))
+        };
+
+        let mut candidates = Vec::new();
+        let mut push = |index: Option<usize>, position: Option<(usize, usize)>| {
+            if let (Some(index), Some(position)) = (index, position) {
+                candidates.push((position, index));
+            }
+        };
+
+        let n_keys: usize = rows.clone().map(|row| count(player, row, is_key)).sum();
+        let key_position = rows.clone().find_map(back);
+
+        if n_keys < self.n_key_objects {
+            push(key, key_position);
+        }
+
+        let enemy = player.toggle();
+        for row in rows.clone().filter(|&row| count(enemy, row, is_fire) > 0) {
+            if count(player, row, is_barrier) == 0 {
+                push(barrier, front(row));
+            }
+            if count(player, row, is_fire) == 0 {
+                push(fire, back(row));
+            }
+        }
+
+        let base_rows = settings.base_span.clone();
+        let other_rows = rows.filter(|row| !settings.base_span.contains(row));
+        for row in base_rows.chain(other_rows) {
+            if count(player, row, is_fire) == 0 {
+                push(fire, back(row));
+            }
+        }
+
+        push(key, key_position);
+        candidates
+    }
+}
+
+impl Default for Greedy {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl Strategy for Greedy {
+    fn decide(
+        &mut self,
+        game: &Game,
+        player: Player,
+        selected_cell: (usize, usize),
+    ) -> Option<Command> {
+        let (position, index) = self.choose(game, player)?;
+        match attempt_placement(game, player, selected_cell, position, index) {
+            Attempt::Command(command) => Some(command),
+            Attempt::Wait | Attempt::Failed => None,
+        }
+    }
+}
+
+/// Returns true if the object kind generates keys.
+fn is_key(kind: &object::Kind) -> bool {
+    matches!(
+        kind,
+        object::Kind::Key {
+            resource: Resource::Keys,
+            ..
+        }
+    )
+}
+
+/// Returns true if the object kind attacks.
+fn is_fire(kind: &object::Kind) -> bool {
+    matches!(kind, object::Kind::Fire { .. })
+}
+
+/// Returns true if the object kind blocks attacks.
+fn is_barrier(kind: &object::Kind) -> bool {
+    matches!(kind, object::Kind::Barrier { .. })
+}
+
+/// Returns the index of the cheapest placement
+/// whose object kind satisfies the predicate.
+///
+/// Placements that cost less energy are preferred,
+/// since the greedy strategy does not plan for energy.
+fn find_placement<P>(game: &Game, player: Player, predicate: P) -> Option<usize>
+where
+    P: Fn(&object::Kind) -> bool,
+{
+    game.players()[player]
+        .placements
+        .iter()
+        .enumerate()
+        .filter(|(_, placement)| predicate(&placement.object.kind))
+        .min_by_key(|(_, placement)| (placement.cost.energy, placement.cost.keys))
+        .map(|(index, _)| index)
+}
+
+/// A built-in strategy,
+/// used to select computer-controlled players in settings.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Builtin {
+    /// The [`Idle`](struct.Idle.html) strategy.
+    Idle,
+    /// The [`Random`](struct.Random.html) strategy with the specified seed.
+    Random(u64),
+    /// The [`Greedy`](struct.Greedy.html) strategy with default parameters.
+    Greedy,
+}
+
+impl Builtin {
+    /// Returns the built-in strategy with the specified name:
+    /// `idle`, `random` or `greedy`.
+    ///
+    /// A random strategy uses the specified seed.
+    pub fn from_name(name: &str, seed: u64) -> Option<Self> {
+        match name {
+            "idle" => Some(Builtin::Idle),
+            "random" => Some(Builtin::Random(seed)),
+            "greedy" => Some(Builtin::Greedy),
+            _ => None,
+        }
+    }
+
+    /// Creates the strategy.
+    pub fn create(self) -> Box<dyn Strategy> {
+        match self {
+            Builtin::Idle => Box::new(Idle),
+            Builtin::Random(seed) => Box::new(Random::new(seed)),
+            Builtin::Greedy => Box::new(Greedy::default()),
+        }
+    }
+}
+
+/// A small deterministic pseudorandom number generator (xorshift64*).
+#[derive(Clone, Debug)]
+struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // the state must never be zero
+        Self {
+            state: seed ^ 0x9E37_79B9_7F4A_7C15 | 1,
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Returns a number in the range `0..bound`.
+    fn below(&mut self, bound: usize) -> usize {
+        use std::convert::TryFrom;
+
+        let bound = u64::try_from(bound).unwrap_or(u64::MAX);
+        usize::try_from(self.next() % bound).unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tick::TICKS_PER_SECOND;
+    use crate::{Catalog, Resources};
+
+    #[test]
+    fn greedy_counters_fire_row_with_barrier() {
+        let mut game = Catalog::builtin().unwrap().game().unwrap();
+        let settings = game.settings().clone();
+        let row = settings.n_rows - 1;
+
+        // wait for the initial placement cooldowns
+        for _ in 0..TICKS_PER_SECOND {
+            game.update();
+        }
+
+        let fire = find_placement(&game, Player::Right, is_fire).unwrap();
+        game.place_object(Player::Right, (row, 2 * settings.n_columns - 1), fire)
+            .unwrap();
+
+        let barrier = find_placement(&game, Player::Left, is_barrier).unwrap();
+        assert_eq!(
+            Greedy::new(0).choose(&game, Player::Left),
+            Some(((row, settings.n_columns - 1), barrier))
+        );
+    }
+
+    #[test]
+    fn greedy_falls_back_to_affordable_placements() {
+        let game = |initial_keys| {
+            let mut catalog = Catalog::builtin().unwrap();
+            catalog.initial_resources.keys = initial_keys;
+            let mut game = catalog.game().unwrap();
+
+            // wait for the initial placement cooldowns
+            for _ in 0..TICKS_PER_SECOND {
+                game.update();
+            }
+            game
+        };
+        let greedy = Greedy::new(0);
+
+        // fire objects cost more than key objects
+        let game = game(20);
+        let (_, first) = greedy.candidates(&game, Player::Left)[0];
+        let (position, index) = greedy.choose(&game, Player::Left).unwrap();
+        assert!(is_fire(&game.players().left.placements[first].object.kind));
+        assert!(is_key(&game.players().left.placements[index].object.kind));
+        assert!(game.check_placement(Player::Left, position, index).is_ok());
+    }
+
+    #[test]
+    fn greedy_waits_if_nothing_is_affordable() {
+        let mut catalog = Catalog::builtin().unwrap();
+        catalog.initial_resources = Resources::default();
+        let game = catalog.game().unwrap();
+        let greedy = Greedy::new(0);
+
+        assert_eq!(
+            greedy.choose(&game, Player::Left),
+            greedy.candidates(&game, Player::Left).first().copied()
+        );
+    }
+}
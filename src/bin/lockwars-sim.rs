@@ -0,0 +1,268 @@
+#![warn(clippy::cargo)]
+#![warn(clippy::pedantic)]
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::multiple_crate_versions)]
+#![allow(clippy::must_use_candidate)]
+
+//! Runs matches between computer-controlled players without a window
+//! and prints statistics, for balance testing.
+
+use anyhow::{anyhow, Result};
+use lockwars::ai;
+use lockwars::controller;
+use lockwars::game::{GameEvent, Outcome};
+use lockwars::{tick, Catalog, Controller, Player, Players, Resource, Resources};
+use std::ffi::OsString;
+
+const USAGE: &str = "\
+usage: lockwars-sim [OPTIONS] [CATALOG]
+
+options:
+    --matches N        number of matches to run (default: 100)
+    --max-time SECS    match length limit in game seconds (default: 600)
+    --seed N           base seed for random strategies (default: 0)
+    --left STRATEGY    strategy of the left player (default: random)
+    --right STRATEGY   strategy of the right player (default: random)
+
+strategies:
+    idle               never do anything
+    random             place random objects on random cells
+    greedy             build economy, then counter and patch enemy fire rows
+    script:PATH        follow the build order in the RON file at PATH";
+
+/// The command line options.
+#[derive(Debug)]
+struct Options {
+    catalog: Option<OsString>,
+    n_matches: u64,
+    max_ticks: u64,
+    seed: u64,
+    strategies: Players<String>,
+}
+
+/// The result of a single match.
+#[derive(Debug)]
+struct MatchResult {
+    outcome: Outcome,
+    ticks: u64,
+    income: Players<Resources<u64>>,
+    spent: Players<Resources<u64>>,
+    refunded: Players<Resources<u64>>,
+    unspent: Players<Resources<u64>>,
+}
+
+fn main() -> Result<()> {
+    let options = parse_options()?;
+
+    let catalog = match &options.catalog {
+        Some(path) => Catalog::load(path)?,
+        None => Catalog::builtin()?,
+    };
+
+    let mut results = Vec::new();
+    for index in 0..options.n_matches {
+        let seed = options.seed.wrapping_add(index);
+        results.push(run_match(&catalog, &options, seed)?);
+    }
+
+    print_statistics(&options, &results);
+    Ok(())
+}
+
+fn parse_options() -> Result<Options> {
+    let mut options = Options {
+        catalog: None,
+        n_matches: 100,
+        max_ticks: 600 * u64::from(tick::TICKS_PER_SECOND),
+        seed: 0,
+        strategies: Players {
+            left: "random".to_owned(),
+            right: "random".to_owned(),
+        },
+    };
+
+    let mut args = std::env::args_os().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .and_then(|value| value.into_string().ok())
+                .ok_or_else(|| anyhow!("missing value for {}\n\n{USAGE}", arg.to_string_lossy()))
+        };
+
+        match arg.to_str() {
+            Some("--matches") => options.n_matches = value()?.parse()?,
+            Some("--max-time") => {
+                let max_time: u64 = value()?.parse()?;
+                options.max_ticks = max_time
+                    .checked_mul(u64::from(tick::TICKS_PER_SECOND))
+                    .ok_or_else(|| {
+                        anyhow!("match length limit {max_time} is too large\n\n{USAGE}")
+                    })?;
+            }
+            Some("--seed") => options.seed = value()?.parse()?,
+            Some("--left") => options.strategies.left = value()?,
+            Some("--right") => options.strategies.right = value()?,
+            Some("--help") => {
+                println!("{USAGE}");
+                std::process::exit(0);
+            }
+            _ if options.catalog.is_none() => options.catalog = Some(arg),
+            _ => {
+                return Err(anyhow!(
+                    "unexpected argument {}\n\n{USAGE}",
+                    arg.to_string_lossy()
+                ))
+            }
+        }
+    }
+
+    Ok(options)
+}
+
+/// Lets the computer control the specified player
+/// with the strategy of the specified name.
+///
+/// Built-in strategies are selected in the controller settings,
+/// while scripted strategies are loaded and handed to the controller.
+fn set_strategy(
+    settings: &mut controller::Settings,
+    scripts: &mut Players<Option<ai::BuildOrder>>,
+    player: Player,
+    name: &str,
+    seed: u64,
+) -> Result<()> {
+    if let Some(path) = name.strip_prefix("script:") {
+        scripts[player] = Some(ai::BuildOrder::load(path)?);
+        return Ok(());
+    }
+
+    let strategy = ai::Builtin::from_name(name, seed)
+        .ok_or_else(|| anyhow!("unknown strategy {name:?}\n\n{USAGE}"))?;
+    settings.computer_players[player] = Some(strategy);
+    Ok(())
+}
+
+fn run_match(catalog: &Catalog, options: &Options, seed: u64) -> Result<MatchResult> {
+    let mut settings = controller::Settings::headless(&catalog.settings);
+    let mut scripts = Players::default();
+    for (player, seed) in [(Player::Left, seed), (Player::Right, !seed)]
+        .iter()
+        .copied()
+    {
+        let name = &options.strategies[player];
+        set_strategy(&mut settings, &mut scripts, player, name, seed)?;
+    }
+
+    let mut controller = Controller::new(settings, catalog.game()?)?;
+    for player in [Player::Left, Player::Right].iter().copied() {
+        if let Some(script) = scripts[player].take() {
+            controller.set_strategy(player, Some(Box::new(script)));
+        }
+    }
+
+    let mut income = Players::<Resources<u64>>::default();
+    let mut spent = Players::<Resources<u64>>::default();
+    let mut refunded = Players::<Resources<u64>>::default();
+    let add = |total: &mut Resources<u64>, amount: &Resources<u32>| {
+        for resource in Resource::ALL.iter().copied() {
+            total[resource] += u64::from(amount[resource]);
+        }
+    };
+
+    while controller.game().outcome() == Outcome::Ongoing
+        && controller.game().tick() < options.max_ticks
+    {
+        controller.step()?;
+
+        for event in controller.events() {
+            match event {
+                GameEvent::Generated {
+                    player,
+                    resource,
+                    amount,
+                    ..
+                } => {
+                    income[*player][*resource] += u64::from(*amount);
+                }
+                GameEvent::Income { player, amount } => {
+                    income[*player].keys += u64::from(*amount);
+                }
+                GameEvent::Placed { player, cost, .. }
+                | GameEvent::Moved { player, cost, .. }
+                | GameEvent::Upgraded { player, cost, .. } => add(&mut spent[*player], cost),
+                GameEvent::Removed { player, refund, .. } => {
+                    add(&mut refunded[*player], refund);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let game = controller.game();
+    let widen = |resources: Resources<u32>| Resources {
+        keys: u64::from(resources.keys),
+        energy: u64::from(resources.energy),
+    };
+    let unspent = Players {
+        left: widen(game.players().left.resources),
+        right: widen(game.players().right.resources),
+    };
+
+    Ok(MatchResult {
+        outcome: game.outcome(),
+        ticks: game.tick(),
+        income,
+        spent,
+        refunded,
+        unspent,
+    })
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn print_statistics(options: &Options, results: &[MatchResult]) {
+    let n_matches = results.len().max(1) as f64;
+    let count = |outcome: Outcome| results.iter().filter(|r| r.outcome == outcome).count();
+    let percentage = |count: usize| count as f64 / n_matches * 100.0;
+
+    let left_wins = count(Outcome::Won(Player::Left));
+    let right_wins = count(Outcome::Won(Player::Right));
+    let draws = count(Outcome::Draw);
+    let timeouts = count(Outcome::Ongoing);
+
+    let average = |value: &dyn Fn(&MatchResult) -> u64| {
+        results.iter().map(value).sum::<u64>() as f64 / n_matches
+    };
+    let average_length = average(&|r| r.ticks) / f64::from(tick::TICKS_PER_SECOND);
+
+    println!("matches:      {}", results.len());
+    println!(
+        "left wins:    {} ({:.1}%)",
+        left_wins,
+        percentage(left_wins)
+    );
+    println!(
+        "right wins:   {} ({:.1}%)",
+        right_wins,
+        percentage(right_wins)
+    );
+    println!("draws:        {} ({:.1}%)", draws, percentage(draws));
+    println!("timeouts:     {} ({:.1}%)", timeouts, percentage(timeouts));
+    println!("average match length: {average_length:.1} s");
+
+    for (player, name) in [(Player::Left, "left"), (Player::Right, "right")]
+        .iter()
+        .copied()
+    {
+        println!("{} player ({}):", name, options.strategies[player]);
+        for resource in Resource::ALL.iter().copied() {
+            println!(
+                "  average income {:.1} {resource}, spent {:.1} {resource}, \
+                 refunded {:.1} {resource}, unspent at end {:.1} {resource}",
+                average(&|r| r.income[player][resource]),
+                average(&|r| r.spent[player][resource]),
+                average(&|r| r.refunded[player][resource]),
+                average(&|r| r.unspent[player][resource]),
+            );
+        }
+    }
+}
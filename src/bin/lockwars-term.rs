@@ -0,0 +1,205 @@
+#![warn(clippy::cargo)]
+#![warn(clippy::pedantic)]
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::multiple_crate_versions)]
+#![allow(clippy::must_use_candidate)]
+
+//! Plays the game in a terminal, without OpenGL.
+
+use anyhow::Result;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use lockwars::controller::{KeyBinding, Speed};
+use lockwars::game::Outcome;
+use lockwars::{launch, Controller, Players, TextRenderer};
+use piston::{Button, ButtonArgs, ButtonState, Key, UpdateArgs};
+use std::io::{self, Stdout, Write};
+use std::time::{Duration, Instant};
+
+const REPLAY_PATH: &str = "replay.ron";
+
+/// The time between two frames.
+const FRAME: Duration = Duration::from_millis(33);
+
+fn main() -> Result<()> {
+    let mut controller =
+        launch::create_controller("lockwars-term", std::env::args_os().skip(1), bindings())?;
+    let renderer = TextRenderer::new();
+
+    {
+        let mut screen = Screen::enter()?;
+        run(&mut controller, renderer, &mut screen)?;
+    }
+
+    if !controller.is_replaying() {
+        controller.replay().save(REPLAY_PATH)?;
+    }
+
+    Ok(())
+}
+
+/// Runs the event loop until <kbd>Esc</kbd> or <kbd>Ctrl</kbd>+<kbd>C</kbd> is pressed.
+///
+/// Terminals report key presses but usually not key releases,
+/// so every key press is passed to the controller as a button release.
+fn run(controller: &mut Controller, renderer: TextRenderer, screen: &mut Screen) -> Result<()> {
+    let mut last_update = Instant::now();
+    screen.draw(&renderer.render(controller))?;
+
+    loop {
+        if event::poll(FRAME.saturating_sub(last_update.elapsed()))? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Release {
+                    continue;
+                }
+                if key.code == KeyCode::Esc
+                    || (key.code == KeyCode::Char('c')
+                        && key.modifiers.contains(KeyModifiers::CONTROL))
+                {
+                    return Ok(());
+                }
+                if let Some(key) = convert_key(key.code) {
+                    controller.button_event(ButtonArgs {
+                        state: ButtonState::Release,
+                        button: Button::Keyboard(key),
+                        scancode: None,
+                    })?;
+                }
+            }
+        }
+
+        let elapsed = last_update.elapsed();
+        if elapsed >= FRAME {
+            last_update += elapsed;
+            if controller.game().outcome() == Outcome::Ongoing {
+                controller.update_event(UpdateArgs {
+                    dt: elapsed.as_secs_f64(),
+                })?;
+            }
+            screen.draw(&renderer.render(controller))?;
+        }
+    }
+}
+
+/// Converts a terminal key code to a piston key.
+fn convert_key(code: KeyCode) -> Option<Key> {
+    let key = match code {
+        KeyCode::Char(c) if c.is_ascii() => Key::from(u32::from(c.to_ascii_lowercase())),
+        KeyCode::F(n) if (1..=12).contains(&n) => Key::from(u32::from(Key::F1) + u32::from(n) - 1),
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::Left => Key::Left,
+        KeyCode::Right => Key::Right,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Delete => Key::Delete,
+        KeyCode::Enter => Key::Return,
+        KeyCode::Tab => Key::Tab,
+        _ => return None,
+    };
+    (key != Key::Unknown).then_some(key)
+}
+
+/// The terminal in raw mode, showing the alternate screen.
+///
+/// The terminal is restored when the screen is dropped,
+/// even if the event loop fails.
+struct Screen {
+    stdout: Stdout,
+}
+
+impl Screen {
+    fn enter() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen, Hide)?;
+        Ok(Self { stdout })
+    }
+
+    fn draw(&mut self, lines: &[String]) -> Result<()> {
+        for (row, line) in (0..).zip(lines) {
+            queue!(
+                self.stdout,
+                MoveTo(0, row),
+                Print(line),
+                Clear(ClearType::UntilNewLine)
+            )?;
+        }
+        queue!(self.stdout, Clear(ClearType::FromCursorDown))?;
+        self.stdout.flush()?;
+        Ok(())
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(self.stdout, Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Creates the button bindings.
+///
+/// Terminals cannot tell the number pad from the main keyboard,
+/// so the right player places objects with the digit row instead.
+fn bindings() -> launch::Bindings {
+    let keys = |keys: &[Key]| keys.iter().copied().map(Button::Keyboard).collect();
+
+    launch::Bindings {
+        key_binding: Players {
+            left: KeyBinding {
+                up: Button::Keyboard(Key::W),
+                down: Button::Keyboard(Key::S),
+                left: Button::Keyboard(Key::A),
+                right: Button::Keyboard(Key::D),
+                remove: Button::Keyboard(Key::G),
+                upgrade: Button::Keyboard(Key::F),
+                relocate: Button::Keyboard(Key::R),
+                place: keys(&[
+                    Key::T,
+                    Key::Y,
+                    Key::U,
+                    Key::I,
+                    Key::O,
+                    Key::P,
+                    Key::LeftBracket,
+                    Key::RightBracket,
+                    Key::Backslash,
+                    Key::Semicolon,
+                    Key::Quote,
+                ]),
+            },
+            right: KeyBinding {
+                up: Button::Keyboard(Key::Up),
+                down: Button::Keyboard(Key::Down),
+                left: Button::Keyboard(Key::Left),
+                right: Button::Keyboard(Key::Right),
+                remove: Button::Keyboard(Key::Backspace),
+                upgrade: Button::Keyboard(Key::Equals),
+                relocate: Button::Keyboard(Key::Period),
+                place: keys(&[
+                    Key::D1,
+                    Key::D2,
+                    Key::D3,
+                    Key::D4,
+                    Key::D5,
+                    Key::D6,
+                    Key::D7,
+                    Key::D8,
+                    Key::D9,
+                    Key::D0,
+                    Key::Minus,
+                ]),
+            },
+        },
+        pause: Button::Keyboard(Key::Space),
+        speeds: vec![
+            (Button::Keyboard(Key::F1), Speed::Half),
+            (Button::Keyboard(Key::F2), Speed::Normal),
+            (Button::Keyboard(Key::F3), Speed::Double),
+            (Button::Keyboard(Key::F4), Speed::Quadruple),
+        ],
+    }
+}
@@ -0,0 +1,255 @@
+//! Data-driven object catalogs.
+//!
+//! A catalog describes the game settings, the object templates,
+//! and the placements available to the players.
+//! Catalogs are stored in the [RON] format,
+//! so that the game can be balanced without recompiling.
+//!
+//! [RON]: https://github.com/ron-rs/ron
+
+use crate::{game, object, player, Cooldown, Game, Object, Player, Players, Resources};
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// The source of the default catalog, [`assets/catalog.ron`].
+///
+/// [`assets/catalog.ron`]: https://github.com/L-F-Stack-Exchange/lockwars/blob/master/assets/catalog.ron
+pub const DEFAULT_SOURCE: &str = include_str!("../assets/catalog.ron");
+
+/// An object catalog.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Catalog {
+    /// The game settings.
+    pub settings: game::Settings,
+
+    /// The amount of each resource each player starts with.
+    pub initial_resources: Resources<u32>,
+
+    /// The object templates, indexed by name.
+    pub objects: BTreeMap<String, Object>,
+
+    /// The placements available to each player.
+    ///
+    /// The index of a placement is its position in the list.
+    pub placements: Vec<PlacementTemplate>,
+
+    /// The upgrade tables for each kind of object.
+    #[serde(default)]
+    pub upgrades: object::Upgrades,
+
+    /// The objects present at the start of the game.
+    #[serde(default)]
+    pub presets: Vec<Preset>,
+}
+
+/// A placement template.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PlacementTemplate {
+    /// The name of the object template to be placed.
+    pub object: String,
+
+    /// The cost of the placement.
+    pub cost: Resources<u32>,
+
+    /// The cooldown of the placement, in ticks.
+    pub cooldown: u32,
+}
+
+/// An object present at the start of the game.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Preset {
+    /// The position of the object.
+    pub position: (usize, usize),
+
+    /// The name of the object template.
+    pub object: String,
+
+    /// The owner of the object.
+    pub owner: Player,
+}
+
+impl Catalog {
+    /// Parses a catalog from a RON string.
+    pub fn from_ron(source: &str) -> Result<Self> {
+        ron::from_str(source).context("cannot parse catalog")
+    }
+
+    /// Parses the default catalog.
+    pub fn builtin() -> Result<Self> {
+        Self::from_ron(DEFAULT_SOURCE).context("cannot parse the default catalog")
+    }
+
+    /// Loads a catalog from the RON file at the specified path.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("cannot read catalog {}", path.display()))?;
+        Self::from_ron(&source)
+    }
+
+    /// Returns the object template with the specified name.
+    pub fn object(&self, name: &str) -> Result<&Object> {
+        self.objects
+            .get(name)
+            .ok_or_else(|| anyhow!("unknown object template {name:?}"))
+    }
+
+    /// Returns the game settings.
+    pub fn settings(&self) -> game::Settings {
+        self.settings.clone()
+    }
+
+    /// Generates the initial data of both players.
+    pub fn players(&self) -> Result<Players<player::Data>> {
+        let placements = self
+            .placements
+            .iter()
+            .map(|template| {
+                Ok(player::Placement {
+                    cooldown: Cooldown::new(template.cooldown),
+                    cost: template.cost,
+                    object: self.object(&template.object)?.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let data = player::Data {
+            resources: self.initial_resources,
+            placements,
+            upgrades: self.upgrades.clone(),
+            losses: 0,
+        };
+
+        Ok(Players {
+            left: data.clone(),
+            right: data,
+        })
+    }
+
+    /// Builds a game from the catalog.
+    ///
+    /// The object templates are checked first with [`Object::check`].
+    ///
+    /// [`Object::check`]: ../object/struct.Object.html#method.check
+    pub fn game(&self) -> Result<Game> {
+        for (name, object) in &self.objects {
+            object
+                .check()
+                .with_context(|| format!("invalid object template {name:?}"))?;
+        }
+
+        let mut builder = game::Builder::new(self.settings())?;
+
+        for preset in &self.presets {
+            builder = builder.object(
+                preset.position,
+                object::Owned {
+                    object: self.object(&preset.object)?.clone(),
+                    owner: preset.owner,
+                },
+            )?;
+        }
+
+        builder.players(self.players()?).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::projectile::Targeting;
+
+    #[test]
+    fn builtin_catalog_builds_a_game() {
+        let catalog = Catalog::builtin().unwrap();
+        let game = catalog.game().unwrap();
+
+        assert_eq!(game.settings().n_columns, catalog.settings.n_columns);
+        for player in [Player::Left, Player::Right].iter().copied() {
+            let data = &game.players()[player];
+            assert_eq!(data.resources, catalog.initial_resources);
+            assert_eq!(data.placements.len(), catalog.placements.len());
+        }
+        let cells = game.cells();
+        for preset in &catalog.presets {
+            let cell = cells[preset.position].borrow();
+            let owner = cell.object.as_ref().map(|object| object.owner);
+            assert_eq!(owner, Some(preset.owner));
+        }
+    }
+
+    #[test]
+    fn invalid_projectile_speed() {
+        for &speed in &[0.0, -1.0, 1000.0, f64::NAN, f64::INFINITY] {
+            let mut catalog = Catalog::builtin().unwrap();
+            if let Some(object::Kind::Fire { speed: s, .. }) = catalog
+                .objects
+                .get_mut("fire")
+                .map(|object| &mut object.kind)
+            {
+                *s = speed;
+            }
+            assert!(catalog.game().is_err());
+        }
+    }
+
+    #[test]
+    fn invalid_piercing_count() {
+        let mut catalog = Catalog::builtin().unwrap();
+        for object in catalog.objects.values_mut() {
+            if let object::Kind::Fire { targeting, .. } = &mut object.kind {
+                *targeting = Targeting::Piercing { count: 0 };
+            }
+        }
+        assert!(catalog.game().is_err());
+    }
+
+    #[test]
+    fn invalid_splash_falloff() {
+        for &falloff in &[-0.5, 1.5, f64::NAN] {
+            let mut catalog = Catalog::builtin().unwrap();
+            for object in catalog.objects.values_mut() {
+                if let object::Kind::Fire { targeting, .. } = &mut object.kind {
+                    *targeting = Targeting::Splash { falloff };
+                }
+            }
+            assert!(catalog.game().is_err());
+        }
+    }
+
+    #[test]
+    fn zero_max_health() {
+        let mut catalog = Catalog::builtin().unwrap();
+        catalog.objects.get_mut("barrier").unwrap().max_health = 0;
+        assert!(catalog.game().is_err());
+    }
+
+    #[test]
+    fn invalid_slow_factor() {
+        for &factor in &[-0.5, 1.5, f64::NAN] {
+            let mut catalog = Catalog::builtin().unwrap();
+            if let Some(object::Kind::Fire { effect, .. }) = catalog
+                .objects
+                .get_mut("fire")
+                .map(|object| &mut object.kind)
+            {
+                *effect = Some(object::Status {
+                    effect: object::Effect::Slow { factor },
+                    duration: 60,
+                    elapsed: 0,
+                });
+            }
+            assert!(catalog.game().is_err());
+        }
+    }
+
+    #[test]
+    fn unknown_object_template() {
+        let mut catalog = Catalog::builtin().unwrap();
+        catalog.placements[0].object = "unknown".to_owned();
+        assert!(catalog.game().is_err());
+    }
+}
@@ -1,16 +1,53 @@
 //! The game controller.
 
-use crate::{Game, Player, Players};
+use crate::ai::{self, Strategy};
+use crate::game::{self, ActionError, GameEvent, Outcome};
+use crate::replay::{self, Record, Replay};
+use crate::tick::Scheduler;
+use crate::{Game, Player, Players, Snapshot};
 use anyhow::{anyhow, Result};
-use piston::{Button, ButtonArgs, ButtonState, UpdateArgs};
+use piston::{Button, ButtonArgs, ButtonState, Key, UpdateArgs};
+use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
+use std::collections::VecDeque;
+use std::time::Duration;
 
 /// A game controller that handles input events.
+///
+/// Player input is translated into [`Command`]s,
+/// which are recorded together with the simulation tick
+/// at which they were issued.
+/// The recording can be retrieved with [`replay`]
+/// and played back with [`from_replay`].
+///
+/// [`Command`]: enum.Command.html
+/// [`replay`]: #method.replay
+/// [`from_replay`]: #method.from_replay
 #[derive(Debug)]
 pub struct Controller {
     settings: Settings,
     game: Game,
     selected_cells: Players<(usize, usize)>,
+    carried_cells: Players<Option<(usize, usize)>>,
+    action_errors: Players<Option<ActionError>>,
+    scheduler: Scheduler,
+    paused: bool,
+    speed: Speed,
+    mode: Mode,
+    initial: Snapshot,
+    initial_selected_cells: Players<(usize, usize)>,
+    records: Vec<Record>,
+    strategies: Players<Option<Box<dyn Strategy>>>,
+    events: Vec<GameEvent>,
+}
+
+/// The source of commands.
+#[derive(Debug)]
+enum Mode {
+    /// Commands are issued by the players.
+    Live,
+    /// Commands are played back from a replay.
+    Playback { pending: VecDeque<Record> },
 }
 
 impl Controller {
@@ -25,62 +62,280 @@ impl Controller {
             .copied()
         {
             let (row, column) = selected_cells[player];
-            if row >= n_rows || column - offset >= n_columns {
+            if row >= n_rows || column.wrapping_sub(offset) >= n_columns {
                 return Err(anyhow!("invalid selected cell"));
             }
         }
 
         Ok(Self {
-            settings,
+            initial: Snapshot::of(&game),
+            scheduler: Scheduler::new(),
+            paused: false,
+            speed: Speed::Normal,
             game,
             selected_cells,
+            carried_cells: Players::default(),
+            action_errors: Players::default(),
+            mode: Mode::Live,
+            initial_selected_cells: selected_cells,
+            records: Vec::new(),
+            strategies: Players {
+                left: settings.computer_players.left.map(ai::Builtin::create),
+                right: settings.computer_players.right.map(ai::Builtin::create),
+            },
+            events: Vec::new(),
+            settings,
         })
     }
 
+    /// Creates a game controller that plays back the specified replay.
+    ///
+    /// Button events are ignored during playback.
+    /// The key binding in `settings` is kept,
+    /// but the selected cells are taken from the replay.
+    pub fn from_replay(settings: Settings, replay: Replay) -> Result<Self> {
+        let game = replay.initial.restore()?;
+        let settings = Settings {
+            selected_cells: replay.selected_cells,
+            ..settings
+        };
+
+        let mut controller = Self::new(settings, game)?;
+        controller.mode = Mode::Playback {
+            pending: replay.records.into(),
+        };
+        Ok(controller)
+    }
+
     /// Returns a reference to the game being controlled.
     pub fn game(&self) -> &Game {
         &self.game
     }
 
+    /// Replaces the game being controlled,
+    /// for example with a game restored from a snapshot.
+    ///
+    /// The selected cells are kept,
+    /// and objects picked up are dropped.
+    /// Playback, if any, is stopped,
+    /// and a new recording is started from the new game.
+    pub fn replace_game(&mut self, game: Game) -> Result<()> {
+        let settings = game.settings();
+        let old_settings = self.game.settings();
+        if settings.n_rows != old_settings.n_rows || settings.n_columns != old_settings.n_columns {
+            return Err(anyhow!("game dimensions do not match"));
+        }
+
+        self.scheduler = Scheduler::new();
+        self.carried_cells = Players::default();
+        self.action_errors = Players::default();
+        self.mode = Mode::Live;
+        self.initial = Snapshot::of(&game);
+        self.initial_selected_cells = self.selected_cells;
+        self.records.clear();
+        self.events.clear();
+        self.game = game;
+        Ok(())
+    }
+
+    /// Returns true if the game is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Pauses or resumes the game.
+    ///
+    /// No ticks are simulated while the game is paused,
+    /// so all cooldowns and status effects are frozen.
+    /// Cooldowns also stay frozen in ticks simulated with [`step`].
+    ///
+    /// [`step`]: #method.step
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Returns the game speed.
+    pub fn speed(&self) -> Speed {
+        self.speed
+    }
+
+    /// Changes the game speed.
+    pub fn set_speed(&mut self, speed: Speed) {
+        self.speed = speed;
+    }
+
+    /// Returns true if the controller is playing back a replay.
+    pub fn is_replaying(&self) -> bool {
+        matches!(self.mode, Mode::Playback { .. })
+    }
+
+    /// Returns the recording of the match so far.
+    pub fn replay(&self) -> Replay {
+        Replay {
+            version: replay::VERSION,
+            initial: self.initial.clone(),
+            selected_cells: self.initial_selected_cells,
+            records: self.records.clone(),
+        }
+    }
+
+    /// Returns a reference to the controller settings.
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
     /// Returns a reference the selected cells.
     pub fn selected_cells(&self) -> &Players<(usize, usize)> {
         &self.selected_cells
     }
 
+    /// Returns the cell of the object picked up by the specified player,
+    /// or `None` if the player is not carrying an object.
+    pub fn carried_cell(&self, player: Player) -> Option<(usize, usize)> {
+        self.carried_cells[player]
+    }
+
+    /// Returns the reason why the last action
+    /// attempted by the specified player was rejected,
+    /// or `None` if it was successful.
+    pub fn action_error(&self, player: Player) -> Option<ActionError> {
+        self.action_errors[player]
+    }
+
+    /// Returns the events of the ticks simulated by the last update event,
+    /// including the events caused by commands issued before them.
+    pub fn events(&self) -> &[GameEvent] {
+        &self.events
+    }
+
+    /// Returns true if the specified player is controlled by the computer.
+    pub fn is_computer(&self, player: Player) -> bool {
+        self.strategies[player].is_some()
+    }
+
+    /// Sets the strategy of the specified player,
+    /// replacing the computer player selected in the settings.
+    ///
+    /// This allows strategies other than the built-in ones.
+    /// `None` hands the player back to the keyboard.
+    pub fn set_strategy(&mut self, player: Player, strategy: Option<Box<dyn Strategy>>) {
+        self.strategies[player] = strategy;
+    }
+
     /// Handles a button event.
+    ///
+    /// The pause and speed buttons work at any time, including playback.
+    /// Other button events are ignored once the game is over,
+    /// while the game is paused,
+    /// or while a replay is being played back.
+    /// Computer-controlled players do not respond to button events.
     pub fn button_event(&mut self, args: ButtonArgs) -> Result<()> {
         if args.state != ButtonState::Release {
             return Ok(());
         }
 
-        for &player in &[Player::Left, Player::Right] {
-            let settings = &self.settings;
-            let game = &mut self.game;
+        if args.button == self.settings.pause {
+            self.paused = !self.paused;
+            return Ok(());
+        }
+        if let Some(&(_, speed)) = self
+            .settings
+            .speeds
+            .iter()
+            .find(|&&(button, _)| button == args.button)
+        {
+            self.speed = speed;
+            return Ok(());
+        }
 
-            let key_binding = &settings.key_binding[player];
-            let selected_cell = self.selected_cells[player];
+        if self.paused || self.game.outcome() != Outcome::Ongoing || self.is_replaying() {
+            return Ok(());
+        }
 
-            if args.button == key_binding.remove {
-                game.clear_cell(player, selected_cell)?;
+        for &player in &[Player::Left, Player::Right] {
+            if self.is_computer(player) {
+                continue;
             }
 
-            if let Some(index) = find(&key_binding.place, &args.button) {
-                game.place_object(player, selected_cell, index)?;
-            }
+            let key_binding = &self.settings.key_binding[player];
 
-            let delta = if args.button == key_binding.up {
-                (-1, 0)
+            let command = if args.button == key_binding.remove {
+                Command::Remove
+            } else if args.button == key_binding.upgrade {
+                Command::Upgrade
+            } else if args.button == key_binding.relocate {
+                Command::Relocate
+            } else if let Some(index) = find(&key_binding.place, &args.button) {
+                Command::Place(index)
+            } else if args.button == key_binding.up {
+                Command::Move(Direction::Up)
             } else if args.button == key_binding.down {
-                (1, 0)
+                Command::Move(Direction::Down)
             } else if args.button == key_binding.left {
-                (0, -1)
+                Command::Move(Direction::Left)
             } else if args.button == key_binding.right {
-                (0, 1)
+                Command::Move(Direction::Right)
             } else {
                 continue;
             };
 
-            self.move_selection(player, delta)?;
+            self.execute(player, command)?;
+        }
+
+        Ok(())
+    }
+
+    /// Executes a command on behalf of the specified player.
+    ///
+    /// The command is recorded at the current tick of the game.
+    /// Commands rejected by the game rules are part of normal play
+    /// and do not cause an error.
+    pub fn execute(&mut self, player: Player, command: Command) -> Result<()> {
+        self.records.push(Record {
+            tick: self.game.tick(),
+            player,
+            command,
+        });
+
+        let selected_cell = self.selected_cells[player];
+
+        match command {
+            Command::Move(direction) => self.move_selection(player, direction.delta())?,
+            Command::Place(index) => {
+                self.action_errors[player] = self
+                    .game
+                    .place_object(player, selected_cell, index)
+                    .map_err(ActionError::from)
+                    .err();
+            }
+            Command::Remove => {
+                self.action_errors[player] = self.game.clear_cell(player, selected_cell).err();
+            }
+            Command::Upgrade => {
+                self.action_errors[player] = self.game.upgrade_object(player, selected_cell).err();
+            }
+            Command::Relocate => match self.carried_cells[player].take() {
+                Some(from) if from != selected_cell => {
+                    self.action_errors[player] =
+                        self.game.move_object(player, from, selected_cell).err();
+                }
+                Some(_) => self.action_errors[player] = None,
+                None => {
+                    let owner = self.game.cells()[selected_cell]
+                        .borrow()
+                        .object
+                        .as_ref()
+                        .map(|object| object.owner);
+                    self.action_errors[player] = match owner {
+                        Some(owner) if owner == player => {
+                            self.carried_cells[player] = Some(selected_cell);
+                            None
+                        }
+                        Some(_) => Some(ActionError::NotOwner),
+                        None => Some(ActionError::Empty),
+                    };
+                }
+            },
         }
 
         Ok(())
@@ -120,8 +375,137 @@ impl Controller {
     }
 
     /// Handles an update event.
-    pub fn update_event(&mut self, _args: UpdateArgs) -> Result<()> {
-        self.game.update()
+    ///
+    /// The time passed since the previous update event,
+    /// multiplied by the game speed,
+    /// is accumulated by a fixed-timestep scheduler,
+    /// and every whole tick due is simulated with [`step`],
+    /// so several ticks are caught up after a slow frame.
+    /// Nothing is simulated while the game is paused.
+    ///
+    /// [`step`]: #method.step
+    pub fn update_event(&mut self, args: UpdateArgs) -> Result<()> {
+        self.events.clear();
+        if self.paused {
+            return Ok(());
+        }
+
+        let dt = Duration::try_from_secs_f64(args.dt * self.speed.factor())
+            .map_err(|_| anyhow!("invalid update interval {}", args.dt))?;
+
+        for _ in 0..self.scheduler.advance(dt) {
+            self.simulate_tick()?;
+        }
+        Ok(())
+    }
+
+    /// Simulates one tick, even if the game is paused.
+    ///
+    /// Cooldowns are not advanced while the game is paused.
+    ///
+    /// During live play, computer-controlled players
+    /// issue their commands before the game is updated.
+    /// During playback, the recorded commands for the tick
+    /// are executed before the game is updated.
+    pub fn step(&mut self) -> Result<()> {
+        self.events.clear();
+        self.simulate_tick()
+    }
+
+    /// Simulates one tick and collects its events.
+    fn simulate_tick(&mut self) -> Result<()> {
+        let tick = self.game.tick();
+        match &mut self.mode {
+            Mode::Live => self.run_strategies()?,
+            Mode::Playback { pending } => {
+                let mut due = Vec::new();
+                while pending.front().is_some_and(|record| record.tick <= tick) {
+                    due.extend(pending.pop_front());
+                }
+                for record in due {
+                    self.execute(record.player, record.command)?;
+                }
+            }
+        }
+
+        self.game.freeze_cooldowns(self.paused);
+        self.game.update();
+        self.events.extend(self.game.take_events());
+        Ok(())
+    }
+
+    /// Lets computer-controlled players issue their commands.
+    fn run_strategies(&mut self) -> Result<()> {
+        if self.game.outcome() != Outcome::Ongoing {
+            return Ok(());
+        }
+
+        for &player in &[Player::Left, Player::Right] {
+            let selected_cell = self.selected_cells[player];
+            let command = match &mut self.strategies[player] {
+                Some(strategy) => strategy.decide(&self.game, player, selected_cell),
+                None => None,
+            };
+            if let Some(command) = command {
+                self.execute(player, command)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A player action.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Command {
+    /// Moves the selection.
+    Move(Direction),
+    /// Places the object with the specified index on the selected cell.
+    Place(usize),
+    /// Removes the object on the selected cell.
+    Remove,
+    /// Upgrades the object on the selected cell.
+    Upgrade,
+    /// Picks up the object on the selected cell,
+    /// or drops the object picked up before on the selected cell.
+    ///
+    /// Dropping an object on the cell it was picked up from
+    /// leaves it in place.
+    Relocate,
+}
+
+/// The direction of a selection movement.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Direction {
+    /// Upwards.
+    Up,
+    /// Downwards.
+    Down,
+    /// To the left.
+    Left,
+    /// To the right.
+    Right,
+}
+
+impl Direction {
+    /// Returns the `(row, column)` offset of the movement.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+}
+
+/// Returns the usual initial selected cells for a game with the specified settings:
+/// the middle row, on the outermost column of each player's territory.
+pub fn initial_selected_cells(settings: &game::Settings) -> Players<(usize, usize)> {
+    let row = settings.n_rows / 2;
+    Players {
+        left: (row, 0),
+        right: (row, 2 * settings.n_columns - 1),
     }
 }
 
@@ -133,6 +517,61 @@ pub struct Settings {
 
     /// Initial selected cells.
     pub selected_cells: Players<(usize, usize)>,
+
+    /// The strategies of computer-controlled players.
+    ///
+    /// `None` indicates a human player.
+    pub computer_players: Players<Option<ai::Builtin>>,
+
+    /// The key for pausing and resuming the game.
+    pub pause: Button,
+
+    /// The keys for changing the game speed.
+    pub speeds: Vec<(Button, Speed)>,
+}
+
+impl Settings {
+    /// Returns settings for a game without keyboard input,
+    /// with all keys unbound, human players on both sides
+    /// and the usual initial selected cells.
+    pub fn headless(settings: &game::Settings) -> Self {
+        Self {
+            key_binding: Players {
+                left: KeyBinding::unbound(),
+                right: KeyBinding::unbound(),
+            },
+            selected_cells: initial_selected_cells(settings),
+            computer_players: Players::default(),
+            pause: UNBOUND,
+            speeds: Vec::new(),
+        }
+    }
+}
+
+/// The speed at which the game is simulated,
+/// relative to real time.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Speed {
+    /// Half speed.
+    Half,
+    /// Normal speed.
+    Normal,
+    /// Double speed.
+    Double,
+    /// Quadruple speed.
+    Quadruple,
+}
+
+impl Speed {
+    /// Returns the factor by which real time is multiplied.
+    pub fn factor(self) -> f64 {
+        match self {
+            Speed::Half => 0.5,
+            Speed::Normal => 1.0,
+            Speed::Double => 2.0,
+            Speed::Quadruple => 4.0,
+        }
+    }
 }
 
 /// Key binding for each player.
@@ -148,6 +587,10 @@ pub struct KeyBinding {
     pub right: Button,
     /// The key for removing an object.
     pub remove: Button,
+    /// The key for upgrading an object.
+    pub upgrade: Button,
+    /// The key for picking up and dropping an object.
+    pub relocate: Button,
     /// The keys for placing an object.
     ///
     /// Each key is assigned an index,
@@ -156,12 +599,31 @@ pub struct KeyBinding {
     pub place: Vec<Button>,
 }
 
+impl KeyBinding {
+    /// Returns a key binding with no keys bound.
+    pub fn unbound() -> Self {
+        Self {
+            up: UNBOUND,
+            down: UNBOUND,
+            left: UNBOUND,
+            right: UNBOUND,
+            remove: UNBOUND,
+            upgrade: UNBOUND,
+            relocate: UNBOUND,
+            place: Vec::new(),
+        }
+    }
+}
+
+/// The button used for actions that are not bound to any key.
+const UNBOUND: Button = Button::Keyboard(Key::Unknown);
+
 /// Returns the index of the first element in the slice
 /// that equals the given value.
-fn find<T, U: ?Sized>(slice: &[T], value: &U) -> Option<usize>
+fn find<T, U>(slice: &[T], value: &U) -> Option<usize>
 where
     T: Borrow<U>,
-    U: Eq,
+    U: Eq + ?Sized,
 {
     for (i, v) in slice.iter().enumerate() {
         if v.borrow() == value {
@@ -170,3 +632,230 @@ where
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Catalog;
+
+    /// The duration of each tick.
+    const TICK: UpdateArgs = UpdateArgs { dt: 1.0 / 60.0 };
+
+    /// The longest match the tests wait for, in ticks.
+    const MAX_TICKS: u64 = 600 * 60;
+
+    fn settings(
+        selected_cells: Players<(usize, usize)>,
+        computer_players: Players<Option<ai::Builtin>>,
+    ) -> Settings {
+        Settings {
+            key_binding: Players {
+                left: KeyBinding::unbound(),
+                right: KeyBinding::unbound(),
+            },
+            selected_cells,
+            computer_players,
+            pause: Button::Keyboard(Key::Space),
+            speeds: vec![(Button::Keyboard(Key::F3), Speed::Double)],
+        }
+    }
+
+    fn release(button: Button) -> ButtonArgs {
+        ButtonArgs {
+            state: ButtonState::Release,
+            button,
+            scancode: None,
+        }
+    }
+
+    fn run(controller: &mut Controller) -> Outcome {
+        while controller.game().outcome() == Outcome::Ongoing
+            && controller.game().tick() < MAX_TICKS
+        {
+            controller.update_event(TICK).unwrap();
+        }
+        controller.game().outcome()
+    }
+
+    fn computer_match(computer_players: Players<Option<ai::Builtin>>) -> Controller {
+        let game = Catalog::builtin().unwrap().game().unwrap();
+        let selected_cells = initial_selected_cells(game.settings());
+        Controller::new(settings(selected_cells, computer_players), game).unwrap()
+    }
+
+    #[test]
+    fn selected_cells_must_be_in_own_territory() {
+        let game = || Catalog::builtin().unwrap().game().unwrap();
+        let valid = initial_selected_cells(game().settings());
+
+        assert!(Controller::new(settings(valid, Players::default()), game()).is_ok());
+        for &(left, right) in &[
+            ((3, 6), valid.right),
+            (valid.left, (3, 0)),
+            (valid.left, (7, 6)),
+        ] {
+            let selected_cells = Players { left, right };
+            assert!(Controller::new(settings(selected_cells, Players::default()), game()).is_err());
+        }
+    }
+
+    #[test]
+    fn replay_reproduces_match() {
+        let game = Catalog::builtin().unwrap().game().unwrap();
+        let selected_cells = initial_selected_cells(game.settings());
+        let mut controller =
+            Controller::new(settings(selected_cells, Players::default()), game).unwrap();
+
+        let commands = [
+            (0, Player::Left, Command::Place(0)),
+            (0, Player::Right, Command::Place(1)),
+            (30, Player::Left, Command::Move(Direction::Up)),
+            (30, Player::Left, Command::Place(1)),
+            (90, Player::Right, Command::Move(Direction::Left)),
+            (90, Player::Right, Command::Place(2)),
+            (240, Player::Left, Command::Remove),
+            (400, Player::Left, Command::Move(Direction::Down)),
+            (400, Player::Left, Command::Relocate),
+            (400, Player::Left, Command::Move(Direction::Left)),
+            (400, Player::Left, Command::Relocate),
+        ];
+        for tick in 0..600 {
+            for &(_, player, command) in commands.iter().filter(|(at, ..)| *at == tick) {
+                controller.execute(player, command).unwrap();
+            }
+            controller.update_event(TICK).unwrap();
+        }
+
+        let replay = Replay::from_ron(&controller.replay().to_ron().unwrap()).unwrap();
+        let mut playback =
+            Controller::from_replay(settings(selected_cells, Players::default()), replay).unwrap();
+        for _ in 0..600 {
+            playback.update_event(TICK).unwrap();
+        }
+
+        let state = |controller: &Controller| Snapshot::of(controller.game()).to_ron().unwrap();
+        assert_ne!(state(&playback), controller.initial.to_ron().unwrap());
+        assert_eq!(state(&playback), state(&controller));
+        for &player in &[Player::Left, Player::Right] {
+            assert_eq!(
+                playback.selected_cells()[player],
+                controller.selected_cells()[player]
+            );
+        }
+    }
+
+    #[test]
+    fn relocate_picks_up_and_drops_objects() {
+        let game = Catalog::builtin().unwrap().game().unwrap();
+        let selected_cells = initial_selected_cells(game.settings());
+        let mut controller =
+            Controller::new(settings(selected_cells, Players::default()), game).unwrap();
+        controller
+            .execute(Player::Left, Command::Move(Direction::Right))
+            .unwrap();
+        let from = controller.selected_cells().left;
+
+        // nothing to pick up
+        controller.execute(Player::Left, Command::Relocate).unwrap();
+        assert_eq!(controller.carried_cell(Player::Left), None);
+        assert_eq!(
+            controller.action_error(Player::Left),
+            Some(ActionError::Empty)
+        );
+
+        // wait for the placement cooldown
+        for _ in 0..60 {
+            controller.update_event(TICK).unwrap();
+        }
+        controller.execute(Player::Left, Command::Place(0)).unwrap();
+        controller.execute(Player::Left, Command::Relocate).unwrap();
+        assert_eq!(controller.carried_cell(Player::Left), Some(from));
+
+        // the relocation cooldown starts when the object is placed
+        controller
+            .execute(Player::Left, Command::Move(Direction::Down))
+            .unwrap();
+        controller.execute(Player::Left, Command::Relocate).unwrap();
+        assert_eq!(controller.carried_cell(Player::Left), None);
+        assert!(matches!(
+            controller.action_error(Player::Left),
+            Some(ActionError::OnCooldown { .. })
+        ));
+
+        for _ in 0..300 {
+            controller.update_event(TICK).unwrap();
+        }
+        controller
+            .execute(Player::Left, Command::Move(Direction::Up))
+            .unwrap();
+        controller.execute(Player::Left, Command::Relocate).unwrap();
+        controller
+            .execute(Player::Left, Command::Move(Direction::Down))
+            .unwrap();
+        controller.execute(Player::Left, Command::Relocate).unwrap();
+        assert_eq!(controller.action_error(Player::Left), None);
+
+        let to = controller.selected_cells().left;
+        let cells = controller.game().cells();
+        assert!(cells[from].borrow().object.is_none());
+        assert!(cells[to].borrow().object.is_some());
+    }
+
+    #[test]
+    fn pause_freezes_and_speed_scales_the_game() {
+        let mut controller = computer_match(Players::default());
+
+        controller
+            .button_event(release(Button::Keyboard(Key::Space)))
+            .unwrap();
+        assert!(controller.is_paused());
+        for _ in 0..60 {
+            controller.update_event(TICK).unwrap();
+        }
+        assert_eq!(controller.game().tick(), 0);
+
+        controller
+            .button_event(release(Button::Keyboard(Key::Space)))
+            .unwrap();
+        controller
+            .button_event(release(Button::Keyboard(Key::F3)))
+            .unwrap();
+        assert_eq!(controller.speed(), Speed::Double);
+        for _ in 0..60 {
+            controller.update_event(TICK).unwrap();
+        }
+        assert_eq!(controller.game().tick(), 120);
+
+        controller.set_paused(true);
+        controller
+            .execute(Player::Left, Command::Move(Direction::Right))
+            .unwrap();
+        controller.execute(Player::Left, Command::Place(0)).unwrap();
+        let remaining = |controller: &Controller| {
+            controller.game().players().left.placements[0]
+                .cooldown
+                .remaining()
+        };
+        let cooldown = remaining(&controller);
+        assert!(cooldown > 0);
+        for _ in 0..10 {
+            controller.step().unwrap();
+        }
+        assert_eq!(controller.game().tick(), 130);
+        assert_eq!(remaining(&controller), cooldown);
+
+        controller.set_paused(false);
+        controller.step().unwrap();
+        assert_eq!(remaining(&controller), cooldown - 1);
+    }
+
+    #[test]
+    fn greedy_beats_idle() {
+        let mut controller = computer_match(Players {
+            left: Some(ai::Builtin::Greedy),
+            right: Some(ai::Builtin::Idle),
+        });
+
+        assert_eq!(run(&mut controller), Outcome::Won(Player::Left));
+    }
+}
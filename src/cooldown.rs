@@ -1,32 +1,210 @@
 //! Cooldown mechanism.
 
-use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
 
 /// A cooldown token.
 ///
-/// The token keeps track of the cooldown state.
-#[derive(Clone, Debug)]
+/// The token keeps track of the cooldown state in ticks.
+/// It does not read the wall clock;
+/// the ticks are passed in through [`advance`].
+///
+/// [`advance`]: #method.advance
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Cooldown {
-    duration: Duration,
-    instant: Instant,
+    duration: u32,
+    #[serde(default)]
+    elapsed: u32,
+    #[serde(default)]
+    delay: u32,
+    #[serde(default)]
+    paused: bool,
 }
 
 impl Cooldown {
-    /// Create a new cooldown token with the given duration.
-    pub fn new(duration: Duration) -> Self {
+    /// Create a new cooldown token with the given duration in ticks.
+    pub fn new(duration: u32) -> Self {
         Self {
             duration,
-            instant: Instant::now(),
+            elapsed: 0,
+            delay: 0,
+            paused: false,
+        }
+    }
+
+    /// Creates a new cooldown token that is already over.
+    pub fn expired(duration: u32) -> Self {
+        Self {
+            elapsed: duration,
+            ..Self::new(duration)
+        }
+    }
+
+    /// Creates a new cooldown token
+    /// that starts counting down after an initial delay in ticks.
+    pub fn delayed(duration: u32, delay: u32) -> Self {
+        Self {
+            delay,
+            ..Self::new(duration)
+        }
+    }
+
+    /// Returns the duration of the cooldown in ticks.
+    pub fn duration(&self) -> u32 {
+        self.duration
+    }
+
+    /// Changes the duration of the cooldown.
+    ///
+    /// The ticks elapsed since the last reset are kept.
+    pub fn set_duration(&mut self, duration: u32) {
+        self.duration = duration;
+    }
+
+    /// Advances the cooldown token by the specified number of ticks.
+    ///
+    /// The initial delay, if any, runs out first.
+    /// A paused cooldown token does not advance.
+    pub fn advance(&mut self, ticks: u32) {
+        if self.paused {
+            return;
         }
+
+        let delayed = ticks.min(self.delay);
+        self.delay -= delayed;
+        self.elapsed = self.elapsed.saturating_add(ticks - delayed);
     }
 
     /// Resets the cooldown token.
+    ///
+    /// The initial delay, if any, is dropped.
     pub fn reset(&mut self) {
-        self.instant = Instant::now();
+        self.elapsed = 0;
+        self.delay = 0;
+    }
+
+    /// Pauses the cooldown token, so that it stops advancing.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resumes a paused cooldown token.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Returns true if the cooldown token is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns the number of ticks remaining until the cooldown is over,
+    /// including the initial delay.
+    pub fn remaining(&self) -> u32 {
+        self.delay
+            .saturating_add(self.duration.saturating_sub(self.elapsed))
+    }
+
+    /// Returns the fraction of the cooldown that has elapsed,
+    /// from `0.0` when it has just started to `1.0` when it is over.
+    ///
+    /// The initial delay counts as no progress,
+    /// even for a cooldown with zero duration.
+    pub fn progress(&self) -> f64 {
+        if self.is_over() {
+            1.0
+        } else if self.duration == 0 {
+            0.0
+        } else {
+            f64::from(self.elapsed) / f64::from(self.duration)
+        }
     }
 
     /// Returns true if the cooldown is over.
     pub fn is_over(&self) -> bool {
-        self.instant.elapsed() >= self.duration
+        self.delay == 0 && self.elapsed >= self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance() {
+        let mut cooldown = Cooldown::new(10);
+        assert!(!cooldown.is_over());
+        assert_eq!(cooldown.remaining(), 10);
+
+        cooldown.advance(4);
+        assert_eq!(cooldown.remaining(), 6);
+        assert!((cooldown.progress() - 0.4).abs() < 1e-9);
+
+        cooldown.advance(6);
+        assert!(cooldown.is_over());
+        assert_eq!(cooldown.remaining(), 0);
+        assert!((cooldown.progress() - 1.0).abs() < f64::EPSILON);
+
+        cooldown.advance(5);
+        assert_eq!(cooldown.remaining(), 0);
+        assert!((cooldown.progress() - 1.0).abs() < f64::EPSILON);
+
+        cooldown.reset();
+        assert_eq!(cooldown.remaining(), 10);
+        assert!(cooldown.progress().abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn expired() {
+        let cooldown = Cooldown::expired(10);
+        assert!(cooldown.is_over());
+        assert_eq!(cooldown.remaining(), 0);
+        assert!((cooldown.progress() - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn delay() {
+        let mut cooldown = Cooldown::delayed(10, 5);
+        assert_eq!(cooldown.remaining(), 15);
+
+        cooldown.advance(3);
+        assert_eq!(cooldown.remaining(), 12);
+        assert!(cooldown.progress().abs() < f64::EPSILON);
+
+        cooldown.advance(7);
+        assert_eq!(cooldown.remaining(), 5);
+        assert!((cooldown.progress() - 0.5).abs() < 1e-9);
+
+        cooldown.reset();
+        assert_eq!(cooldown.remaining(), 10);
+    }
+
+    #[test]
+    fn zero_duration() {
+        let mut cooldown = Cooldown::delayed(0, 5);
+        assert!(!cooldown.is_over());
+        assert_eq!(cooldown.remaining(), 5);
+        assert!(cooldown.progress().abs() < f64::EPSILON);
+
+        cooldown.advance(5);
+        assert!(cooldown.is_over());
+        assert!((cooldown.progress() - 1.0).abs() < f64::EPSILON);
+
+        assert!(Cooldown::new(0).is_over());
+    }
+
+    #[test]
+    fn pause() {
+        let mut cooldown = Cooldown::delayed(10, 2);
+        cooldown.pause();
+        assert!(cooldown.is_paused());
+
+        cooldown.advance(20);
+        assert_eq!(cooldown.remaining(), 12);
+
+        cooldown.resume();
+        assert!(!cooldown.is_paused());
+
+        cooldown.advance(4);
+        assert_eq!(cooldown.remaining(), 8);
     }
 }
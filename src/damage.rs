@@ -0,0 +1,198 @@
+//! Damage types and armor.
+//!
+//! Every hit on an object has a [`DamageType`],
+//! and every object has an [`Armor`] that reduces the damage it takes.
+//! The damage actually taken is calculated by [`mitigate`] alone,
+//! so that the balance of damage types can be tuned in one place.
+//!
+//! [`DamageType`]: enum.DamageType.html
+//! [`Armor`]: struct.Armor.html
+//! [`mitigate`]: fn.mitigate.html
+
+use serde::{Deserialize, Serialize};
+
+/// An amount of damage of a certain type.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Damage {
+    /// The amount of damage before mitigation.
+    pub amount: u32,
+
+    /// The type of the damage.
+    pub kind: DamageType,
+}
+
+/// The type of damage.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum DamageType {
+    /// Ordinary damage.
+    #[default]
+    Physical,
+    /// Damage from fire and burning.
+    Fire,
+    /// Damage that ignores flat armor.
+    Piercing,
+    /// Heavy damage meant for breaking barriers.
+    Siege,
+}
+
+/// The armor of an object.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Armor {
+    /// The amount subtracted from each hit after resistances,
+    /// except for piercing damage.
+    pub flat: u32,
+
+    /// The fraction of damage resisted for each type of damage.
+    pub resistances: Resistances,
+}
+
+/// The fraction of damage resisted for each type of damage.
+///
+/// A resistance of `0.5` halves the damage taken,
+/// and a negative resistance is a weakness:
+/// `-1.0` doubles the damage taken.
+/// Resistances above `1.0` are treated as `1.0`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Resistances {
+    /// The resistance to physical damage.
+    pub physical: f64,
+    /// The resistance to fire damage.
+    pub fire: f64,
+    /// The resistance to piercing damage.
+    pub piercing: f64,
+    /// The resistance to siege damage.
+    pub siege: f64,
+}
+
+impl Resistances {
+    /// Returns the resistance to the specified type of damage.
+    pub fn get(&self, kind: DamageType) -> f64 {
+        match kind {
+            DamageType::Physical => self.physical,
+            DamageType::Fire => self.fire,
+            DamageType::Piercing => self.piercing,
+            DamageType::Siege => self.siege,
+        }
+    }
+}
+
+/// Returns the amount of damage taken by an object with the specified armor.
+///
+/// The damage is first scaled by the resistance to its type
+/// and rounded to the nearest integer,
+/// then reduced by the flat armor unless it is piercing damage.
+pub fn mitigate(damage: Damage, armor: &Armor) -> u32 {
+    let resistance = armor.resistances.get(damage.kind).min(1.0);
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let amount = (f64::from(damage.amount) * (1.0 - resistance)).round() as u32;
+
+    match damage.kind {
+        DamageType::Piercing => amount,
+        DamageType::Physical | DamageType::Fire | DamageType::Siege => {
+            amount.saturating_sub(armor.flat)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [DamageType; 4] = [
+        DamageType::Physical,
+        DamageType::Fire,
+        DamageType::Piercing,
+        DamageType::Siege,
+    ];
+
+    fn damage(amount: u32, kind: DamageType) -> Damage {
+        Damage { amount, kind }
+    }
+
+    fn resistant(kind: DamageType, resistance: f64) -> Armor {
+        let mut resistances = Resistances::default();
+        match kind {
+            DamageType::Physical => resistances.physical = resistance,
+            DamageType::Fire => resistances.fire = resistance,
+            DamageType::Piercing => resistances.piercing = resistance,
+            DamageType::Siege => resistances.siege = resistance,
+        }
+        Armor {
+            flat: 0,
+            resistances,
+        }
+    }
+
+    #[test]
+    fn no_armor() {
+        for kind in ALL.iter().copied() {
+            assert_eq!(mitigate(damage(20, kind), &Armor::default()), 20);
+        }
+    }
+
+    #[test]
+    fn resistance_applies_to_its_type_only() {
+        for kind in ALL.iter().copied() {
+            let armor = resistant(kind, 0.5);
+            for other in ALL.iter().copied() {
+                let expected = if other == kind { 10 } else { 20 };
+                assert_eq!(mitigate(damage(20, other), &armor), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn flat_armor() {
+        let armor = Armor {
+            flat: 5,
+            ..Armor::default()
+        };
+        assert_eq!(mitigate(damage(20, DamageType::Physical), &armor), 15);
+        assert_eq!(mitigate(damage(20, DamageType::Fire), &armor), 15);
+        assert_eq!(mitigate(damage(20, DamageType::Siege), &armor), 15);
+        assert_eq!(mitigate(damage(3, DamageType::Physical), &armor), 0);
+    }
+
+    #[test]
+    fn piercing_ignores_flat_armor() {
+        let armor = Armor {
+            flat: 5,
+            ..resistant(DamageType::Piercing, 0.5)
+        };
+        assert_eq!(mitigate(damage(20, DamageType::Piercing), &armor), 10);
+    }
+
+    #[test]
+    fn flat_armor_after_resistance() {
+        let armor = Armor {
+            flat: 5,
+            ..resistant(DamageType::Physical, 0.5)
+        };
+        assert_eq!(mitigate(damage(20, DamageType::Physical), &armor), 5);
+    }
+
+    #[test]
+    fn negative_resistance() {
+        let armor = resistant(DamageType::Siege, -1.0);
+        assert_eq!(mitigate(damage(20, DamageType::Siege), &armor), 40);
+    }
+
+    #[test]
+    fn resistance_above_one() {
+        let armor = resistant(DamageType::Fire, 2.0);
+        assert_eq!(mitigate(damage(20, DamageType::Fire), &armor), 0);
+    }
+
+    #[test]
+    fn rounding() {
+        let armor = resistant(DamageType::Physical, 0.5);
+        assert_eq!(mitigate(damage(5, DamageType::Physical), &armor), 3);
+        assert_eq!(mitigate(damage(3, DamageType::Physical), &armor), 2);
+
+        let armor = resistant(DamageType::Physical, 0.75);
+        assert_eq!(mitigate(damage(5, DamageType::Physical), &armor), 1);
+    }
+}
@@ -3,11 +3,39 @@
 //! # Division line
 //!
 //! The division line separates the two players' territories.
+//! The left player's territory consists of the columns `0..n_columns`,
+//! and the right player's territory consists of
+//! the columns `n_columns..2 * n_columns`.
+//! Players can only place objects in their own territory,
+//! and can only clear cells that hold their own objects.
+//!
+//! # Bases
+//!
+//! Each player has a base on the outer edge of their territory,
+//! spanning the rows in [`Settings::base_span`].
+//! A projectile that finds no target on a row within the span
+//! damages the enemy base instead.
+//! The game is over as soon as a base is destroyed.
+//!
+//! # Projectiles
+//!
+//! Fire objects do not damage their targets instantly.
+//! Instead, they fire [projectiles] that travel along the row.
+//!
+//! [projectiles]: ../projectile/index.html
+//!
+//! [`Settings::base_span`]: struct.Settings.html#structfield.base_span
 
-use crate::{object, player, Player, Players};
+use crate::damage::{self, Damage};
+use crate::projectile::{self, Targeting};
+use crate::tick::TICKS_PER_SECOND;
+use crate::{object, player, Player, Players, Projectile, Resource, Resources};
 use anyhow::{anyhow, Context, Result};
 use ndarray::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
+use std::error;
+use std::fmt;
 use std::ops::Range;
 
 /// The game state.
@@ -18,6 +46,11 @@ pub struct Game {
     settings: Settings,
     cells: Array2<RefCell<Cell>>,
     players: Players<player::Data>,
+    bases: Players<Base>,
+    projectiles: Vec<Projectile>,
+    tick: u64,
+    cooldowns_frozen: bool,
+    events: Vec<GameEvent>,
 }
 
 impl Game {
@@ -27,7 +60,7 @@ impl Game {
     }
 
     /// Returns the cells.
-    pub fn cells(&self) -> ArrayView2<RefCell<Cell>> {
+    pub fn cells(&self) -> ArrayView2<'_, RefCell<Cell>> {
         self.cells.view()
     }
 
@@ -36,135 +69,690 @@ impl Game {
         &self.players
     }
 
+    /// Returns the bases.
+    pub fn bases(&self) -> &Players<Base> {
+        &self.bases
+    }
+
+    /// Returns the projectiles in flight.
+    pub fn projectiles(&self) -> &[Projectile] {
+        &self.projectiles
+    }
+
+    /// Returns the outcome of the game.
+    pub fn outcome(&self) -> Outcome {
+        match (
+            self.bases.left.is_destroyed(),
+            self.bases.right.is_destroyed(),
+        ) {
+            (false, false) => Outcome::Ongoing,
+            (false, true) => Outcome::Won(Player::Left),
+            (true, false) => Outcome::Won(Player::Right),
+            (true, true) => Outcome::Draw,
+        }
+    }
+
+    /// Freezes or unfreezes all cooldowns.
+    ///
+    /// While the cooldowns are frozen, updates do not advance
+    /// the cooldowns of objects, of their relocations, or of placements.
+    /// Whether the cooldowns are frozen is not part of the saved game state.
+    pub fn freeze_cooldowns(&mut self, frozen: bool) {
+        self.cooldowns_frozen = frozen;
+    }
+
+    /// Takes the events that happened since the last call.
+    ///
+    /// Events accumulate until they are taken.
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Returns the number of ticks simulated so far.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Returns the columns that make up the territory of the specified player.
+    pub fn territory(&self, player: Player) -> Range<usize> {
+        let n_columns = self.settings.n_columns;
+        match player {
+            Player::Left => 0..n_columns,
+            Player::Right => n_columns..2 * n_columns,
+        }
+    }
+
     /// Clears the cell at the specified position.
-    pub fn clear_cell(&mut self, _player: Player, position: (usize, usize)) -> Result<()> {
-        let cell = self
-            .cells
-            .get(position)
-            .ok_or_else(|| anyhow!("invalid position"))?;
-        cell.borrow_mut().object = None;
+    ///
+    /// The cell must be in the player's territory,
+    /// and the object in the cell, if any, must be owned by the player.
+    /// If the removal mode is [`Removal::Sell`],
+    /// part of the cost of the object is refunded,
+    /// up to the resource caps.
+    ///
+    /// [`Removal::Sell`]: enum.Removal.html#variant.Sell
+    pub fn clear_cell(
+        &mut self,
+        player: Player,
+        position: (usize, usize),
+    ) -> Result<(), ActionError> {
+        let cell = self.territory_cell(player, position)?;
+        let mut cell = cell.borrow_mut();
+        if let Some(object) = &cell.object {
+            if object.owner != player {
+                return Err(ActionError::NotOwner);
+            }
+        }
+        let object = cell.object.take();
+        drop(cell);
+
+        if let Some(object) = object {
+            let refund = object.object.refund(self.settings.removal.refund());
+
+            let refund = self.players[player]
+                .resources
+                .gain(&refund, &self.settings.caps);
+
+            self.events.push(GameEvent::Removed {
+                player,
+                position,
+                object,
+                refund,
+            });
+        }
         Ok(())
     }
 
     /// Places an object at the specified position
     /// according to the specified object index.
     ///
-    /// The player's keys are deducted accordingly.
-    /// Returns `true` if the placement is successful,
-    /// or `false` if the players does not have enough keys
-    /// or if the index is invalid.
+    /// The position must be an empty cell in the player's territory.
+    /// The cost is deducted from the player's resources
+    /// and the placement cooldown is reset.
+    /// The game is not modified if the placement fails.
     pub fn place_object(
         &mut self,
         player: Player,
         position: (usize, usize),
         index: usize,
-    ) -> Result<bool> {
+    ) -> Result<(), PlacementError> {
+        self.check_placement(player, position, index)?;
+
         let player_data = &mut self.players[player];
+        let placement = &mut player_data.placements[index];
+
+        placement.cooldown.reset();
+        player_data.resources.spend(&placement.cost);
 
+        self.cells[position].borrow_mut().object = Some(object::Owned {
+            object: placement.generate_object(),
+            owner: player,
+        });
+        self.events.push(GameEvent::Placed {
+            player,
+            position,
+            index,
+            cost: placement.cost,
+        });
+        Ok(())
+    }
+
+    /// Checks whether the specified placement is allowed,
+    /// without modifying the game.
+    pub fn check_placement(
+        &self,
+        player: Player,
+        position: (usize, usize),
+        index: usize,
+    ) -> Result<(), PlacementError> {
         let cell = self
             .cells
-            .get_mut(position)
-            .ok_or_else(|| anyhow!("invalid position"))?;
+            .get(position)
+            .ok_or(PlacementError::InvalidPosition)?;
+        if !self.territory(player).contains(&position.1) {
+            return Err(PlacementError::OutOfTerritory);
+        }
+        if cell.borrow().object.is_some() {
+            return Err(PlacementError::Occupied);
+        }
 
-        let placement = match player_data.placements.get_mut(index) {
-            None => return Ok(false),
-            Some(placement) => placement,
-        };
+        let player_data = &self.players[player];
+        let placement = player_data
+            .placements
+            .get(index)
+            .ok_or(PlacementError::InvalidIndex)?;
 
-        let cooldown = &mut placement.cooldown;
-        if !cooldown.is_over() {
-            return Ok(false);
+        if !placement.cooldown.is_over() {
+            return Err(PlacementError::OnCooldown {
+                remaining: placement.cooldown.remaining(),
+            });
+        }
+        if let Some(resource) = player_data.resources.shortage(&placement.cost) {
+            let needed = placement.cost[resource];
+            let have = player_data.resources[resource];
+            return Err(match resource {
+                Resource::Keys => PlacementError::InsufficientKeys { needed, have },
+                Resource::Energy => PlacementError::InsufficientResources {
+                    resource,
+                    needed,
+                    have,
+                },
+            });
         }
-        cooldown.reset();
 
-        let keys = &mut player_data.keys;
-        *keys = match keys.checked_sub(placement.cost) {
-            None => return Ok(false),
-            Some(remaining_keys) => remaining_keys,
-        };
+        Ok(())
+    }
 
-        cell.borrow_mut().object = Some(object::Owned {
-            object: placement.generate_object(),
-            owner: player,
+    /// Moves an object to an empty cell.
+    ///
+    /// Both cells must be in the player's territory,
+    /// and the object must be owned by the player and movable.
+    /// The cost is deducted from the player's resources
+    /// and the relocation cooldown of the object is reset.
+    /// The game is not modified if the move fails.
+    pub fn move_object(
+        &mut self,
+        player: Player,
+        from: (usize, usize),
+        to: (usize, usize),
+    ) -> Result<(), ActionError> {
+        self.territory_cell(player, from)?;
+        self.territory_cell(player, to)?;
+        if from == to {
+            return Err(ActionError::SamePosition);
+        }
+        if self.cells[to].borrow().object.is_some() {
+            return Err(ActionError::Occupied);
+        }
+
+        let mut from_cell = self.cells[from].borrow_mut();
+        let object = from_cell.object.as_mut().ok_or(ActionError::Empty)?;
+        if object.owner != player {
+            return Err(ActionError::NotOwner);
+        }
+        let relocation = object
+            .object
+            .relocation
+            .as_mut()
+            .ok_or(ActionError::Immovable)?;
+
+        if !relocation.cooldown.is_over() {
+            return Err(ActionError::OnCooldown {
+                remaining: relocation.cooldown.remaining(),
+            });
+        }
+        let resources = &mut self.players[player].resources;
+        if let Some(resource) = resources.shortage(&relocation.cost) {
+            return Err(ActionError::InsufficientResources {
+                resource,
+                needed: relocation.cost[resource],
+                have: resources[resource],
+            });
+        }
+
+        let cost = relocation.cost;
+        resources.spend(&cost);
+        relocation.cooldown.reset();
+        self.cells[to].borrow_mut().object = from_cell.object.take();
+        drop(from_cell);
+
+        self.events.push(GameEvent::Moved {
+            player,
+            from,
+            to,
+            cost,
+        });
+        Ok(())
+    }
+
+    /// Upgrades the object at the specified position.
+    ///
+    /// The object must be owned by the player,
+    /// and the player must be able to pay for the next upgrade
+    /// in the upgrade table for the kind of the object.
+    /// The game is not modified if the upgrade fails.
+    pub fn upgrade_object(
+        &mut self,
+        player: Player,
+        position: (usize, usize),
+    ) -> Result<(), ActionError> {
+        self.territory_cell(player, position)?;
+
+        let mut cell = self.cells[position].borrow_mut();
+        let object = cell.object.as_mut().ok_or(ActionError::Empty)?;
+        if object.owner != player {
+            return Err(ActionError::NotOwner);
+        }
+
+        let player_data = &mut self.players[player];
+        let upgrade = player_data
+            .upgrades
+            .next(&object.object)
+            .ok_or(ActionError::MaxLevel)?;
+
+        if let Some(resource) = player_data.resources.shortage(&upgrade.cost) {
+            return Err(ActionError::InsufficientResources {
+                resource,
+                needed: upgrade.cost[resource],
+                have: player_data.resources[resource],
+            });
+        }
+
+        player_data.resources.spend(&upgrade.cost);
+        object.object.apply_upgrade(upgrade);
+
+        self.events.push(GameEvent::Upgraded {
+            player,
+            position,
+            level: object.object.level,
+            cost: upgrade.cost,
         });
-        Ok(true)
+        Ok(())
     }
 
-    /// Updates the state of the game.
-    pub fn update(&mut self) -> Result<()> {
+    /// Returns the cell at the specified position,
+    /// which must be in the player's territory.
+    fn territory_cell(
+        &self,
+        player: Player,
+        position: (usize, usize),
+    ) -> Result<&RefCell<Cell>, ActionError> {
+        let cell = self
+            .cells
+            .get(position)
+            .ok_or(ActionError::InvalidPosition)?;
+        if self.territory(player).contains(&position.1) {
+            Ok(cell)
+        } else {
+            Err(ActionError::OutOfTerritory)
+        }
+    }
+
+    /// Updates the state of the game by one tick.
+    ///
+    /// All cooldowns are advanced by one tick,
+    /// and passive income is paid at the end of every second of game time.
+    /// Nothing happens once the game is over.
+    #[allow(clippy::too_many_lines)]
+    pub fn update(&mut self) {
         use object::Kind;
 
+        if self.outcome() != Outcome::Ongoing {
+            return;
+        }
+
+        self.tick += 1;
+        self.advance();
+        self.move_projectiles();
+        let ticks_into_second = self.tick % u64::from(TICKS_PER_SECOND);
+        if ticks_into_second == 0 {
+            self.pay_income();
+        }
+
         let settings = &self.settings;
 
-        for ((row, _column), cell) in self.cells.indexed_iter() {
+        for (position, cell) in self.cells.indexed_iter() {
             let mut cell = cell.borrow_mut();
-            let object = match &mut cell.object {
-                None => continue,
-                Some(object) => object,
+            let Some(object) = &mut cell.object else {
+                continue;
             };
             let owner = object.owner;
 
             match object.object.kind {
                 Kind::Key {
                     generation,
+                    resource,
                     ref mut cooldown,
                 } => {
                     let players = &mut self.players;
                     if cooldown.is_over() {
                         cooldown.reset();
 
-                        let keys = &mut players[owner].keys;
-                        *keys = keys.saturating_add(generation).min(settings.max_keys);
+                        let mut amount = Resources::default();
+                        amount[resource] = generation;
+                        let gained = players[owner].resources.gain(&amount, &settings.caps);
+
+                        self.events.push(GameEvent::Generated {
+                            position,
+                            player: owner,
+                            resource,
+                            amount: gained[resource],
+                        });
                     }
                 }
                 Kind::Fire {
                     damage,
+                    damage_type,
+                    speed,
+                    targeting,
+                    ref effect,
                     ref mut cooldown,
                 } => {
                     if cooldown.is_over() {
                         cooldown.reset();
 
-                        if let Some(target) = self.find_target(row, owner.toggle()) {
-                            target.borrow_mut().receive_damage(damage);
-                        }
+                        self.projectiles.push(Projectile::new(
+                            owner,
+                            position,
+                            speed,
+                            Damage {
+                                amount: damage,
+                                kind: damage_type,
+                            },
+                            targeting,
+                            effect.clone(),
+                        ));
+                        self.events.push(GameEvent::Fired {
+                            from: position,
+                            damage,
+                        });
                     }
                 }
                 Kind::Barrier {} => {}
+                Kind::Healer {
+                    amount,
+                    range,
+                    ref mut cooldown,
+                } => {
+                    if cooldown.is_over() {
+                        cooldown.reset();
+
+                        for target in self.neighbors(position, range) {
+                            let mut target_cell = self.cells[target].borrow_mut();
+                            let Some(object) = &mut target_cell.object else {
+                                continue;
+                            };
+                            if object.owner != owner {
+                                continue;
+                            }
+
+                            let healed = object.object.heal(amount);
+                            if healed > 0 {
+                                self.events.push(GameEvent::Healed {
+                                    from: position,
+                                    to: target,
+                                    amount: healed,
+                                });
+                            }
+                        }
+                    }
+                }
+                Kind::Caster {
+                    ref status,
+                    range,
+                    ref mut cooldown,
+                } => {
+                    if cooldown.is_over() {
+                        cooldown.reset();
+
+                        let affected = if status.effect.is_harmful() {
+                            owner.toggle()
+                        } else {
+                            owner
+                        };
+                        for target in self.neighbors(position, range) {
+                            let mut target_cell = self.cells[target].borrow_mut();
+                            let Some(object) = &mut target_cell.object else {
+                                continue;
+                            };
+                            if object.owner != affected {
+                                continue;
+                            }
+
+                            object.object.apply_status(status.clone());
+                            self.events.push(GameEvent::StatusApplied {
+                                position: target,
+                                effect: status.effect,
+                            });
+                        }
+                    }
+                }
             }
         }
+    }
 
-        Ok(())
+    /// Pays one second of passive income to both players.
+    fn pay_income(&mut self) {
+        let income = &self.settings.income;
+
+        for player in [Player::Left, Player::Right].iter().copied() {
+            let player_data = &self.players[player];
+            let enemy_losses = self.players[player.toggle()].losses;
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let interest = (f64::from(player_data.resources.keys) * income.interest_rate) as u32;
+            let comeback = player_data.losses.saturating_sub(enemy_losses);
+
+            let amount = Resources {
+                keys: income
+                    .base
+                    .saturating_add(
+                        income
+                            .interest_cap
+                            .map_or(interest, |cap| interest.min(cap)),
+                    )
+                    .saturating_add(comeback.saturating_mul(income.comeback)),
+                ..Resources::default()
+            };
+            let gained = self.players[player]
+                .resources
+                .gain(&amount, &self.settings.caps);
+
+            if gained.keys > 0 {
+                self.events.push(GameEvent::Income {
+                    player,
+                    amount: gained.keys,
+                });
+            }
+        }
     }
 
-    /// Find a target on the specified row.
+    /// Returns the positions within the specified distance
+    /// from the specified position, excluding the position itself.
     ///
-    /// `player` specifies the targeted player.
-    fn find_target(&self, row: usize, player: Player) -> Option<&RefCell<Cell>> {
-        fn find_in<I>(game: &Game, row: usize, column_range: I) -> Option<&RefCell<Cell>>
-        where
-            I: Iterator<Item = usize>,
-        {
-            for column in column_range {
-                let cell = &game.cells[(row, column)];
-                if cell.borrow().object.is_some() {
-                    return Some(cell);
+    /// The distance is measured in steps between adjacent cells.
+    fn neighbors(
+        &self,
+        (row, column): (usize, usize),
+        range: usize,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (n_rows, n_total_columns) = self.cells.dim();
+
+        let rows = row.saturating_sub(range)..(row.saturating_add(range) + 1).min(n_rows);
+        let columns =
+            column.saturating_sub(range)..(column.saturating_add(range) + 1).min(n_total_columns);
+
+        rows.flat_map(move |r| columns.clone().map(move |c| (r, c)))
+            .filter(move |&(r, c)| {
+                let distance = r.abs_diff(row) + c.abs_diff(column);
+                distance > 0 && distance <= range
+            })
+    }
+
+    /// Advances all cooldowns and status effects by one tick,
+    /// and applies the burn damage taken in that tick.
+    fn advance(&mut self) {
+        let mut burns = Vec::new();
+        for (position, cell) in self.cells.indexed_iter() {
+            if let Some(object) = &mut cell.borrow_mut().object {
+                let burn = object.object.tick(self.cooldowns_frozen);
+                if burn > 0 {
+                    burns.push((position, burn));
                 }
             }
-            None
+        }
+        for (position, burn) in burns {
+            self.deal(Target::Object(position), burn);
         }
 
-        let n_columns = self.settings.n_columns;
-        let n_total_columns = 2 * n_columns;
+        if self.cooldowns_frozen {
+            return;
+        }
+        for player in [Player::Left, Player::Right].iter().copied() {
+            for placement in &mut self.players[player].placements {
+                placement.cooldown.advance(1);
+            }
+        }
+    }
 
-        match player {
-            Player::Left => find_in(self, row, (0..n_columns).rev()),
-            Player::Right => find_in(self, row, n_columns..n_total_columns),
+    /// Moves all projectiles by the distance travelled in one tick,
+    /// and applies the damage of projectiles that hit something.
+    fn move_projectiles(&mut self) {
+        use std::convert::TryFrom;
+
+        let n_total_columns = 2 * self.settings.n_columns;
+        let mut projectiles = std::mem::take(&mut self.projectiles);
+
+        projectiles.retain_mut(|projectile| {
+            let enemy = projectile.owner.toggle();
+            let row = projectile.row;
+
+            if let Some(column) = projectile.tick() {
+                let Some(column) = usize::try_from(column)
+                    .ok()
+                    .filter(|&column| column < n_total_columns)
+                else {
+                    // the projectile has left the game area,
+                    // normally on the enemy side
+                    let enemy_side = match enemy {
+                        Player::Left => column < 0,
+                        Player::Right => column >= 0,
+                    };
+                    if enemy_side && self.settings.base_span.contains(&row) {
+                        self.hit(Target::Base(enemy), projectile.damage);
+                    }
+                    return false;
+                };
+
+                if self.is_target(projectile, (row, column)) {
+                    self.strike(projectile, (row, column));
+                    if !projectile.pierces_further() {
+                        return false;
+                    }
+                }
+            }
+
+            true
+        });
+
+        self.projectiles = projectiles;
+    }
+
+    /// Returns true if the projectile hits the object at the specified position
+    /// when entering the cell.
+    fn is_target(&self, projectile: &Projectile, (row, column): (usize, usize)) -> bool {
+        use object::Kind;
+
+        let enemy = projectile.owner.toggle();
+        let is_enemy = |column: usize, include_barriers: bool| {
+            self.cells[(row, column)]
+                .borrow()
+                .object
+                .as_ref()
+                .is_some_and(|object| {
+                    object.owner == enemy
+                        && (include_barriers || !matches!(object.object.kind, Kind::Barrier { .. }))
+                })
+        };
+
+        match projectile.targeting {
+            Targeting::Single | Targeting::Splash { .. } | Targeting::Piercing { .. } => {
+                is_enemy(column, true)
+            }
+            Targeting::Lobbed => {
+                // only land on the furthest non-barrier enemy object
+                let mut further = match projectile.owner {
+                    Player::Left => column + 1..2 * self.settings.n_columns,
+                    Player::Right => 0..column,
+                };
+                is_enemy(column, false) && !further.any(|column| is_enemy(column, false))
+            }
+        }
+    }
+
+    /// Applies the damage and status effect of the projectile
+    /// to the object at the specified position,
+    /// and the damage to its neighbors for splash damage.
+    fn strike(&mut self, projectile: &mut Projectile, position: (usize, usize)) {
+        projectile.hits += 1;
+        self.hit(Target::Object(position), projectile.damage);
+
+        if let Some(status) = &projectile.effect {
+            if let Some(object) = &mut self.cells[position].borrow_mut().object {
+                object.object.apply_status(status.clone());
+                self.events.push(GameEvent::StatusApplied {
+                    position,
+                    effect: status.effect,
+                });
+            }
+        }
+
+        if let Targeting::Splash { falloff } = projectile.targeting {
+            #[allow(
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss,
+                clippy::cast_precision_loss
+            )]
+            let amount = (f64::from(projectile.damage.amount) * falloff) as u32;
+            if amount == 0 {
+                return;
+            }
+            let damage = Damage {
+                amount,
+                ..projectile.damage
+            };
+
+            let enemy = projectile.owner.toggle();
+            let neighbors: Vec<_> = self.neighbors(position, 1).collect();
+            for neighbor in neighbors {
+                let is_enemy = self.cells[neighbor]
+                    .borrow()
+                    .object
+                    .as_ref()
+                    .is_some_and(|object| object.owner == enemy);
+                if is_enemy {
+                    self.hit(Target::Object(neighbor), damage);
+                }
+            }
+        }
+    }
+
+    /// Deals the specified damage to the target,
+    /// mitigated by the armor of the target object.
+    ///
+    /// Bases have no armor.
+    fn hit(&mut self, target: Target, damage: Damage) {
+        let amount = match target {
+            Target::Object(position) => match &self.cells[position].borrow().object {
+                Some(object) => damage::mitigate(damage, &object.object.armor),
+                None => return,
+            },
+            Target::Base(_) => damage.amount,
+        };
+        self.deal(target, amount);
+    }
+
+    /// Deals the specified amount of damage to the target
+    /// without mitigation.
+    fn deal(&mut self, target: Target, damage: u32) {
+        self.events.push(GameEvent::Hit { target, damage });
+
+        match target {
+            Target::Object(position) => {
+                let destroyed = self.cells[position].borrow_mut().receive_damage(damage);
+                if let Some(object) = destroyed {
+                    self.players[object.owner].losses += 1;
+                    self.events
+                        .push(GameEvent::ObjectDestroyed { position, object });
+                }
+            }
+            Target::Base(player) => self.bases[player].receive_damage(damage),
         }
     }
 }
 
 /// The game settings.
-#[derive(Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Settings {
     /// The number of columns on each player's side.
     ///
@@ -177,8 +765,63 @@ pub struct Settings {
     /// The rows that the bases span.
     pub base_span: Range<usize>,
 
-    /// The maximum amount of keys each player can have.
-    pub max_keys: u32,
+    /// The maximum amount of each resource each player can have.
+    pub caps: Resources<u32>,
+
+    /// The initial health of each base.
+    pub base_health: u32,
+
+    /// What happens to the resources spent on an object when it is removed.
+    #[serde(default)]
+    pub removal: Removal,
+
+    /// The passive income of each player.
+    #[serde(default)]
+    pub income: Income,
+}
+
+/// Passive key income, paid to each player every second of game time.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Income {
+    /// The amount of keys paid every second.
+    pub base: u32,
+
+    /// The fraction of the unspent keys paid as interest every second.
+    pub interest_rate: f64,
+
+    /// The maximum amount of interest paid every second.
+    ///
+    /// `None` indicates that interest is not capped.
+    pub interest_cap: Option<u32>,
+
+    /// The amount of keys paid every second
+    /// for each object the player has lost more than the other player.
+    pub comeback: u32,
+}
+
+/// The removal mode.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum Removal {
+    /// Removed objects are destroyed without a refund.
+    #[default]
+    Destroy,
+    /// Removed objects are sold for part of their placement cost,
+    /// scaled by their remaining health.
+    Sell {
+        /// The fraction of the cost refunded for an object at full health.
+        refund: f64,
+    },
+}
+
+impl Removal {
+    /// Returns the fraction of the cost refunded for an object at full health.
+    pub fn refund(self) -> f64 {
+        match self {
+            Removal::Destroy => 0.0,
+            Removal::Sell { refund } => refund,
+        }
+    }
 }
 
 /// Builds a game.
@@ -187,6 +830,9 @@ pub struct Builder {
     settings: Settings,
     cells: Array2<RefCell<Cell>>,
     players: Option<Players<player::Data>>,
+    bases: Option<Players<Base>>,
+    projectiles: Vec<Projectile>,
+    tick: u64,
 }
 
 impl Builder {
@@ -200,6 +846,12 @@ impl Builder {
             Err(anyhow!("base must span at least one row"))
         } else if settings.base_span.end > settings.n_rows {
             Err(anyhow!("base must not exceed game area"))
+        } else if settings.base_health == 0 {
+            Err(anyhow!("base must have positive health"))
+        } else if !(0.0..).contains(&settings.income.interest_rate) {
+            Err(anyhow!("interest rate must not be negative"))
+        } else if !(0.0..=1.0).contains(&settings.removal.refund()) {
+            Err(anyhow!("refund must be a fraction between 0 and 1"))
         } else {
             let n_rows = settings.n_rows;
             let n_total_columns = settings.n_columns * 2;
@@ -210,35 +862,405 @@ impl Builder {
                     RefCell::new(Cell::empty())
                 }),
                 players: None,
+                bases: None,
+                projectiles: Vec::new(),
+                tick: 0,
             })
         }
     }
 
     /// Presets an object.
+    ///
+    /// The object is checked with [`Object::check`].
+    ///
+    /// [`Object::check`]: ../object/struct.Object.html#method.check
     pub fn object(mut self, index: (usize, usize), owned_object: object::Owned) -> Result<Self> {
+        owned_object.object.check().context("invalid object")?;
         let cell = self.cells.get_mut(index).context("cannot preset object")?;
         cell.borrow_mut().object = Some(owned_object);
         Ok(self)
     }
 
     /// Sets player data.
+    #[must_use]
     pub fn players(mut self, players: Players<player::Data>) -> Self {
         self.players = Some(players);
         self
     }
 
+    /// Sets the state of the bases.
+    ///
+    /// By default, both bases have full health.
+    #[must_use]
+    pub fn bases(mut self, bases: Players<Base>) -> Self {
+        self.bases = Some(bases);
+        self
+    }
+
+    /// Sets the projectiles in flight.
+    #[must_use]
+    pub fn projectiles(mut self, projectiles: Vec<Projectile>) -> Self {
+        self.projectiles = projectiles;
+        self
+    }
+
+    /// Sets the number of ticks already simulated.
+    ///
+    /// By default, the game starts at tick zero.
+    #[must_use]
+    pub fn tick(mut self, tick: u64) -> Self {
+        self.tick = tick;
+        self
+    }
+
     /// Builds a game.
+    ///
+    /// The objects to be placed are checked with [`Object::check`].
+    ///
+    /// [`Object::check`]: ../object/struct.Object.html#method.check
     pub fn finish(self) -> Result<Game> {
+        for projectile in &self.projectiles {
+            if projectile.row >= self.settings.n_rows {
+                return Err(anyhow!("projectile must be within game area"));
+            }
+            projectile::check_speed(projectile.speed).context("invalid projectile")?;
+            projectile.targeting.check().context("invalid projectile")?;
+            if let Some(status) = &projectile.effect {
+                status.effect.check().context("invalid projectile")?;
+            }
+        }
+
+        let players = self
+            .players
+            .ok_or_else(|| anyhow!("player data must be provided"))?;
+        for player in [Player::Left, Player::Right].iter().copied() {
+            for placement in &players[player].placements {
+                placement.object.check().context("invalid placement")?;
+            }
+        }
+
         Ok(Game {
-            settings: self.settings,
             cells: self.cells,
-            players: self
-                .players
-                .ok_or_else(|| anyhow!("player data must be provided"))?,
+            players,
+            bases: self.bases.unwrap_or(Players {
+                left: Base::new(self.settings.base_health),
+                right: Base::new(self.settings.base_health),
+            }),
+            settings: self.settings,
+            projectiles: self.projectiles,
+            tick: self.tick,
+            cooldowns_frozen: false,
+            events: Vec::new(),
         })
     }
 }
 
+/// Something that happened in the game.
+///
+/// Events are collected by the game
+/// and retrieved with [`Game::take_events`].
+///
+/// [`Game::take_events`]: struct.Game.html#method.take_events
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    /// A key object generated a resource.
+    Generated {
+        /// The position of the key object.
+        position: (usize, usize),
+        /// The player who received the resource.
+        player: Player,
+        /// The resource generated.
+        resource: Resource,
+        /// The amount received,
+        /// after limiting to the resource cap.
+        amount: u32,
+    },
+    /// A player received passive income.
+    Income {
+        /// The player who received the income.
+        player: Player,
+        /// The amount of keys received,
+        /// after limiting to the resource cap.
+        amount: u32,
+    },
+    /// A fire object fired a projectile.
+    Fired {
+        /// The position of the fire object.
+        from: (usize, usize),
+        /// The amount of damage carried by the projectile.
+        damage: u32,
+    },
+    /// A target took damage,
+    /// either from a projectile hit or from burning.
+    Hit {
+        /// The target that took damage.
+        target: Target,
+        /// The amount of damage dealt, after armor.
+        damage: u32,
+    },
+    /// A healer object restored the health of an object.
+    Healed {
+        /// The position of the healer object.
+        from: (usize, usize),
+        /// The position of the healed object.
+        to: (usize, usize),
+        /// The amount of health restored.
+        amount: u32,
+    },
+    /// An object was destroyed by damage.
+    ObjectDestroyed {
+        /// The position of the object.
+        position: (usize, usize),
+        /// The destroyed object.
+        object: object::Owned,
+    },
+    /// A player placed an object.
+    Placed {
+        /// The player who placed the object.
+        player: Player,
+        /// The position of the object.
+        position: (usize, usize),
+        /// The placement index.
+        index: usize,
+        /// The resources spent.
+        cost: Resources<u32>,
+    },
+    /// A status effect was applied to an object.
+    StatusApplied {
+        /// The position of the object.
+        position: (usize, usize),
+        /// The effect applied.
+        effect: object::Effect,
+    },
+    /// A player moved an object.
+    Moved {
+        /// The player who moved the object.
+        player: Player,
+        /// The old position of the object.
+        from: (usize, usize),
+        /// The new position of the object.
+        to: (usize, usize),
+        /// The resources spent.
+        cost: Resources<u32>,
+    },
+    /// A player upgraded an object.
+    Upgraded {
+        /// The player who upgraded the object.
+        player: Player,
+        /// The position of the object.
+        position: (usize, usize),
+        /// The new level of the object.
+        level: u32,
+        /// The resources spent.
+        cost: Resources<u32>,
+    },
+    /// A player removed an object.
+    Removed {
+        /// The player who removed the object.
+        player: Player,
+        /// The position of the object.
+        position: (usize, usize),
+        /// The removed object.
+        object: object::Owned,
+        /// The resources refunded.
+        refund: Resources<u32>,
+    },
+}
+
+/// The target of an attack.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Target {
+    /// The object at the specified position.
+    Object((usize, usize)),
+    /// The base of the specified player.
+    Base(Player),
+}
+
+/// An error that occurs when a placement is rejected.
+///
+/// The game is not modified when a placement is rejected.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PlacementError {
+    /// The position is outside the game area.
+    InvalidPosition,
+    /// The position is outside the player's territory.
+    OutOfTerritory,
+    /// The cell is already occupied by an object.
+    Occupied,
+    /// The placement index is invalid.
+    InvalidIndex,
+    /// The placement cooldown is not over yet.
+    OnCooldown {
+        /// The number of ticks remaining until the cooldown is over.
+        remaining: u32,
+    },
+    /// The player does not have enough keys.
+    InsufficientKeys {
+        /// The cost of the placement in keys.
+        needed: u32,
+        /// The number of keys the player owns.
+        have: u32,
+    },
+    /// The player does not have enough of a resource other than keys.
+    InsufficientResources {
+        /// The resource that is short.
+        resource: Resource,
+        /// The cost of the placement in that resource.
+        needed: u32,
+        /// The amount of that resource the player owns.
+        have: u32,
+    },
+}
+
+impl fmt::Display for PlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        ActionError::from(*self).fmt(f)
+    }
+}
+
+impl error::Error for PlacementError {}
+
+impl From<PlacementError> for ActionError {
+    fn from(error: PlacementError) -> Self {
+        match error {
+            PlacementError::InvalidPosition => ActionError::InvalidPosition,
+            PlacementError::OutOfTerritory => ActionError::OutOfTerritory,
+            PlacementError::Occupied => ActionError::Occupied,
+            PlacementError::InvalidIndex => ActionError::InvalidIndex,
+            PlacementError::OnCooldown { remaining } => ActionError::OnCooldown { remaining },
+            PlacementError::InsufficientKeys { needed, have } => {
+                ActionError::InsufficientResources {
+                    resource: Resource::Keys,
+                    needed,
+                    have,
+                }
+            }
+            PlacementError::InsufficientResources {
+                resource,
+                needed,
+                have,
+            } => ActionError::InsufficientResources {
+                resource,
+                needed,
+                have,
+            },
+        }
+    }
+}
+
+/// An error that occurs when a player action violates the game rules.
+///
+/// The game is not modified when an action is rejected.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ActionError {
+    /// The position is outside the game area.
+    InvalidPosition,
+    /// The position is outside the player's territory.
+    OutOfTerritory,
+    /// The object in the cell is owned by the other player.
+    NotOwner,
+    /// The cell is already occupied by an object.
+    Occupied,
+    /// There is no object in the cell.
+    Empty,
+    /// The placement index is invalid.
+    InvalidIndex,
+    /// The cooldown of the action is not over yet.
+    OnCooldown {
+        /// The number of ticks remaining until the cooldown is over.
+        remaining: u32,
+    },
+    /// The object cannot be moved.
+    Immovable,
+    /// The destination of a move is the cell the object is on.
+    SamePosition,
+    /// The object is at the maximum level.
+    MaxLevel,
+    /// The player does not have enough of a resource.
+    InsufficientResources {
+        /// The resource that is short.
+        resource: Resource,
+        /// The cost of the action in that resource.
+        needed: u32,
+        /// The amount of that resource the player owns.
+        have: u32,
+    },
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ActionError::InvalidPosition => write!(f, "invalid position"),
+            ActionError::OutOfTerritory => {
+                write!(f, "position is outside the player's territory")
+            }
+            ActionError::NotOwner => write!(f, "object is owned by the other player"),
+            ActionError::Occupied => write!(f, "cell is already occupied"),
+            ActionError::Empty => write!(f, "cell is empty"),
+            ActionError::InvalidIndex => write!(f, "invalid placement index"),
+            ActionError::OnCooldown { remaining } => {
+                write!(f, "action is on cooldown for {remaining} ticks")
+            }
+            ActionError::Immovable => write!(f, "object cannot be moved"),
+            ActionError::SamePosition => write!(f, "object is already at the destination"),
+            ActionError::MaxLevel => write!(f, "object is at the maximum level"),
+            ActionError::InsufficientResources {
+                resource,
+                needed,
+                have,
+            } => {
+                write!(
+                    f,
+                    "action needs {needed} {resource}, but only {have} are available"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for ActionError {}
+
+/// The outcome of a game.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    /// The game is still going on.
+    Ongoing,
+    /// The specified player has won the game.
+    Won(Player),
+    /// Both bases were destroyed at the same time.
+    Draw,
+}
+
+/// A player's base.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Base {
+    /// The health of the base.
+    pub health: u32,
+
+    /// The maximum health of the base.
+    pub max_health: u32,
+}
+
+impl Base {
+    /// Creates a base with full health.
+    pub fn new(health: u32) -> Self {
+        Self {
+            health,
+            max_health: health,
+        }
+    }
+
+    /// Returns true if the base has been destroyed.
+    pub fn is_destroyed(&self) -> bool {
+        self.health == 0
+    }
+
+    /// Receives the specified amount of damage.
+    pub fn receive_damage(&mut self, damage: u32) {
+        self.health = self.health.saturating_sub(damage);
+    }
+}
+
 /// A cell.
 #[derive(Clone, Debug)]
 pub struct Cell {
@@ -255,17 +1277,957 @@ impl Cell {
     /// Receives the specified amount of damage,
     /// if an object is present.
     ///
-    /// The object is removed if its health runs out.
-    pub fn receive_damage(&mut self, damage: u32) {
-        let object = match &mut self.object {
-            None => return,
-            Some(object) => &mut object.object,
-        };
+    /// Shields on the object absorb the damage first.
+    /// The object is removed if its health runs out,
+    /// in which case the removed object is returned.
+    pub fn receive_damage(&mut self, damage: u32) -> Option<object::Owned> {
+        let object = &mut self.object.as_mut()?.object;
+        let damage = object.absorb(damage);
 
         if object.health > damage {
             object.health -= damage;
+            None
         } else {
-            self.object = None;
+            self.object.take()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cooldown, Object};
+
+    /// The number of ticks in one second of game time.
+    const SECOND: u32 = TICKS_PER_SECOND;
+
+    /// Updates the game by the specified number of ticks.
+    fn run(game: &mut Game, ticks: u32) {
+        for _ in 0..ticks {
+            game.update();
+        }
+    }
+
+    fn settings() -> Settings {
+        Settings {
+            n_columns: 2,
+            n_rows: 3,
+            base_span: 1..2,
+            caps: keys(100),
+            base_health: 30,
+            removal: Removal::Destroy,
+            income: Income::default(),
+        }
+    }
+
+    /// Returns resources that consist of the specified amount of keys.
+    fn keys(amount: u32) -> Resources<u32> {
+        Resources {
+            keys: amount,
+            ..Resources::default()
+        }
+    }
+
+    /// Returns player data with the specified amount of keys
+    /// and two placements of barriers that cost 20 keys:
+    /// the first without cooldown,
+    /// and the second with a cooldown of ten seconds that has just started.
+    fn players(amount: u32) -> Players<player::Data> {
+        let placement = |cooldown| player::Placement {
+            cooldown: Cooldown::new(cooldown * SECOND),
+            cost: keys(20),
+            object: Object {
+                kind: object::Kind::Barrier {},
+                health: 50,
+                max_health: 50,
+                armor: damage::Armor::default(),
+                level: 0,
+                cost: Resources::default(),
+                relocation: None,
+                statuses: Vec::new(),
+            },
+        };
+        let data = || player::Data {
+            resources: keys(amount),
+            placements: vec![placement(0), placement(10)],
+            upgrades: object::Upgrades::default(),
+            losses: 0,
+        };
+        Players {
+            left: data(),
+            right: data(),
+        }
+    }
+
+    fn owned(kind: object::Kind, health: u32, owner: Player) -> object::Owned {
+        object::Owned {
+            object: Object {
+                kind,
+                health,
+                max_health: health,
+                armor: damage::Armor::default(),
+                level: 0,
+                cost: Resources::default(),
+                relocation: None,
+                statuses: Vec::new(),
+            },
+            owner,
+        }
+    }
+
+    #[test]
+    fn keys_are_generated_when_the_cooldown_is_over() {
+        let key = object::Kind::Key {
+            resource: Resource::Keys,
+            generation: 10,
+            cooldown: Cooldown::new(SECOND),
+        };
+        let mut game = Builder::new(settings())
+            .unwrap()
+            .object((0, 0), owned(key, 10, Player::Left))
+            .unwrap()
+            .players(players(0))
+            .finish()
+            .unwrap();
+        run(&mut game, SECOND - 1);
+        assert_eq!(game.players().left.resources.keys, 0);
+
+        run(&mut game, 1);
+        assert_eq!(game.players().left.resources.keys, 10);
+        assert_eq!(game.tick(), u64::from(SECOND));
+
+        run(&mut game, SECOND);
+        assert_eq!(game.players().left.resources.keys, 20);
+    }
+
+    /// Returns a fire object kind that deals 10 damage per second
+    /// with projectiles of the specified speed.
+    fn fire(speed: f64) -> object::Kind {
+        targeted_fire(speed, Targeting::Single)
+    }
+
+    /// Returns a fire object kind like [`fire`]
+    /// with the specified targeting mode.
+    fn targeted_fire(speed: f64, targeting: Targeting) -> object::Kind {
+        object::Kind::Fire {
+            damage: 10,
+            damage_type: damage::DamageType::Physical,
+            speed,
+            targeting,
+            effect: None,
+            cooldown: Cooldown::new(SECOND),
+        }
+    }
+
+    #[test]
+    fn targeting_modes_select_their_targets() {
+        let key = || object::Kind::Key {
+            resource: Resource::Keys,
+            generation: 0,
+            cooldown: Cooldown::new(SECOND),
+        };
+        let positions = [(1, 4), (1, 5), (1, 6), (0, 4), (2, 4)];
+
+        // returns the health of the objects at `positions`
+        // after a single projectile has crossed the board
+        let health_after_shot = |targeting| {
+            let mut game = Builder::new(Settings {
+                n_columns: 4,
+                ..settings()
+            })
+            .unwrap()
+            .object(
+                (1, 0),
+                owned(targeted_fire(10.0, targeting), 10, Player::Left),
+            )
+            .unwrap()
+            .object((1, 4), owned(object::Kind::Barrier {}, 100, Player::Right))
+            .unwrap();
+            for &position in &positions[1..] {
+                game = game
+                    .object(position, owned(key(), 100, Player::Right))
+                    .unwrap();
+            }
+            let mut game = game.players(players(0)).finish().unwrap();
+            run(&mut game, SECOND);
+            run(&mut game, 54);
+            assert!(game.projectiles().is_empty());
+
+            positions
+                .iter()
+                .map(|&position| {
+                    game.cells()[position]
+                        .borrow()
+                        .object
+                        .as_ref()
+                        .map_or(0, |object| object.object.health)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            health_after_shot(Targeting::Single),
+            [90, 100, 100, 100, 100]
+        );
+        assert_eq!(
+            health_after_shot(Targeting::Splash { falloff: 0.5 }),
+            [90, 95, 100, 95, 95]
+        );
+        assert_eq!(
+            health_after_shot(Targeting::Piercing { count: 2 }),
+            [90, 90, 100, 100, 100]
+        );
+        assert_eq!(
+            health_after_shot(Targeting::Lobbed),
+            [100, 100, 90, 100, 100]
+        );
+    }
+
+    #[test]
+    fn fire_destroys_barrier_at_a_known_time() {
+        let mut game = Builder::new(settings())
+            .unwrap()
+            .object((0, 0), owned(fire(10.0), 10, Player::Left))
+            .unwrap()
+            .object((0, 3), owned(object::Kind::Barrier {}, 30, Player::Right))
+            .unwrap()
+            .players(players(0))
+            .finish()
+            .unwrap();
+
+        // projectiles are fired every second
+        // and take three ticks to reach the barrier
+        for _ in 0..32 {
+            run(&mut game, 6);
+            assert!(game.cells()[(0, 3)].borrow().object.is_some());
+        }
+
+        run(&mut game, 6);
+        assert!(game.cells()[(0, 3)].borrow().object.is_none());
+    }
+
+    #[test]
+    fn projectiles_are_intercepted_by_new_objects() {
+        let mut game = Builder::new(settings())
+            .unwrap()
+            .object((1, 0), owned(fire(1.0), 10, Player::Left))
+            .unwrap()
+            .object((1, 1), owned(object::Kind::Barrier {}, 10, Player::Left))
+            .unwrap()
+            .players(players(100))
+            .finish()
+            .unwrap();
+        run(&mut game, SECOND);
+        assert_eq!(game.projectiles().len(), 1);
+
+        // the projectile passes the friendly barrier
+        run(&mut game, SECOND);
+        assert!((game.projectiles()[0].column - 1.5).abs() < 1e-9);
+        assert_eq!(
+            game.cells()[(1, 1)]
+                .borrow()
+                .object
+                .as_ref()
+                .unwrap()
+                .object
+                .health,
+            10
+        );
+
+        // and hits the barrier placed in its path instead of the base
+        game.place_object(Player::Right, (1, 3), 0).unwrap();
+        run(&mut game, 3 * SECOND / 2 + 5);
+        assert_eq!(
+            game.cells()[(1, 3)]
+                .borrow()
+                .object
+                .as_ref()
+                .unwrap()
+                .object
+                .health,
+            40
+        );
+        assert_eq!(game.bases().right.health, 30);
+    }
+
+    #[test]
+    fn healers_repair_adjacent_friendly_objects() {
+        let healer = object::Kind::Healer {
+            amount: 15,
+            range: 1,
+            cooldown: Cooldown::new(SECOND),
+        };
+        let damaged = |owner| object::Owned {
+            object: Object {
+                kind: object::Kind::Barrier {},
+                health: 10,
+                max_health: 30,
+                armor: damage::Armor::default(),
+                level: 0,
+                cost: Resources::default(),
+                relocation: None,
+                statuses: Vec::new(),
+            },
+            owner,
+        };
+        let mut game = Builder::new(settings())
+            .unwrap()
+            .object((0, 0), owned(healer, 10, Player::Left))
+            .unwrap()
+            .object((0, 1), damaged(Player::Left))
+            .unwrap()
+            .object((1, 0), damaged(Player::Right))
+            .unwrap()
+            .object((1, 1), damaged(Player::Left))
+            .unwrap()
+            .players(players(0))
+            .finish()
+            .unwrap();
+        let health = |game: &Game, position| {
+            game.cells()[position]
+                .borrow()
+                .object
+                .as_ref()
+                .map(|object| object.object.health)
+        };
+        run(&mut game, SECOND);
+        assert_eq!(health(&game, (0, 1)), Some(25));
+        assert_eq!(
+            game.take_events(),
+            vec![GameEvent::Healed {
+                from: (0, 0),
+                to: (0, 1),
+                amount: 15,
+            }]
+        );
+
+        // health is capped at the maximum health
+        run(&mut game, SECOND);
+        assert_eq!(health(&game, (0, 1)), Some(30));
+
+        // enemy objects and objects out of range are not healed
+        assert_eq!(health(&game, (1, 0)), Some(10));
+        assert_eq!(health(&game, (1, 1)), Some(10));
+    }
+
+    #[test]
+    fn updates_and_actions_emit_events() {
+        let key = object::Kind::Key {
+            resource: Resource::Keys,
+            generation: 10,
+            cooldown: Cooldown::new(SECOND),
+        };
+        let barrier = owned(object::Kind::Barrier {}, 10, Player::Right);
+        let mut game = Builder::new(settings())
+            .unwrap()
+            .object((0, 0), owned(fire(10.0), 10, Player::Left))
+            .unwrap()
+            .object((0, 3), barrier.clone())
+            .unwrap()
+            .object((2, 0), owned(key, 10, Player::Left))
+            .unwrap()
+            .players(players(95))
+            .finish()
+            .unwrap();
+        run(&mut game, SECOND);
+        assert_eq!(
+            game.take_events(),
+            vec![
+                GameEvent::Fired {
+                    from: (0, 0),
+                    damage: 10,
+                },
+                GameEvent::Generated {
+                    position: (2, 0),
+                    player: Player::Left,
+                    resource: Resource::Keys,
+                    amount: 5,
+                },
+            ]
+        );
+
+        run(&mut game, 18);
+        assert_eq!(
+            game.take_events(),
+            vec![
+                GameEvent::Hit {
+                    target: Target::Object((0, 3)),
+                    damage: 10,
+                },
+                GameEvent::ObjectDestroyed {
+                    position: (0, 3),
+                    object: barrier,
+                },
+            ]
+        );
+
+        game.place_object(Player::Left, (1, 1), 0).unwrap();
+        let placed = game.cells()[(1, 1)].borrow().object.clone().unwrap();
+        game.clear_cell(Player::Left, (1, 1)).unwrap();
+        // clearing an empty cell emits no event
+        game.clear_cell(Player::Left, (1, 1)).unwrap();
+        assert_eq!(
+            game.take_events(),
+            vec![
+                GameEvent::Placed {
+                    player: Player::Left,
+                    position: (1, 1),
+                    index: 0,
+                    cost: keys(20),
+                },
+                GameEvent::Removed {
+                    player: Player::Left,
+                    position: (1, 1),
+                    object: placed,
+                    refund: Resources::default(),
+                },
+            ]
+        );
+        assert!(game.take_events().is_empty());
+    }
+
+    #[test]
+    fn fire_destroys_base_at_a_known_time() {
+        let mut game = Builder::new(settings())
+            .unwrap()
+            .object((1, 0), owned(fire(4.0), 10, Player::Left))
+            .unwrap()
+            .object((0, 3), owned(fire(4.0), 10, Player::Right))
+            .unwrap()
+            .players(players(0))
+            .finish()
+            .unwrap();
+
+        // projectiles leave the game area one second after being fired
+        for &expected_health in &[30, 20, 10] {
+            run(&mut game, SECOND);
+            assert_eq!(game.bases().right.health, expected_health);
+            assert_eq!(game.outcome(), Outcome::Ongoing);
+        }
+
+        run(&mut game, SECOND);
+        assert_eq!(game.outcome(), Outcome::Won(Player::Left));
+        assert_eq!(game.bases().left.health, 30);
+
+        // nothing happens once the game is over
+        let tick = game.tick();
+        run(&mut game, SECOND);
+        assert_eq!(game.tick(), tick);
+    }
+
+    #[test]
+    fn placement_only_in_own_territory() {
+        let mut game = Builder::new(settings())
+            .unwrap()
+            .players(players(100))
+            .finish()
+            .unwrap();
+
+        for column in 2..4 {
+            assert_eq!(
+                game.place_object(Player::Left, (0, column), 0),
+                Err(PlacementError::OutOfTerritory)
+            );
+            assert!(game.cells()[(0, column)].borrow().object.is_none());
+        }
+        assert_eq!(
+            game.place_object(Player::Left, (3, 0), 0),
+            Err(PlacementError::InvalidPosition)
+        );
+        assert_eq!(game.players().left.resources.keys, 100);
+
+        assert_eq!(game.place_object(Player::Left, (0, 1), 0), Ok(()));
+        assert_eq!(game.place_object(Player::Right, (0, 2), 0), Ok(()));
+        assert_eq!(
+            game.place_object(Player::Left, (0, 1), 0),
+            Err(PlacementError::Occupied)
+        );
+    }
+
+    fn status(effect: object::Effect, secs: u32) -> object::Status {
+        object::Status {
+            effect,
+            duration: secs * SECOND,
+            elapsed: 0,
+        }
+    }
+
+    #[test]
+    fn status_effects_expire_after_their_duration() {
+        use object::Effect;
+
+        let key = || object::Kind::Key {
+            resource: Resource::Keys,
+            generation: 10,
+            cooldown: Cooldown::new(SECOND),
+        };
+        let with_status = |kind, owner, status| {
+            let mut object = owned(kind, 30, owner);
+            object.object.statuses.push(status);
+            object
+        };
+        let mut game = Builder::new(settings())
+            .unwrap()
+            .object(
+                (0, 0),
+                with_status(
+                    object::Kind::Barrier {},
+                    Player::Left,
+                    status(
+                        Effect::Burn {
+                            damage_per_second: 10,
+                        },
+                        2,
+                    ),
+                ),
+            )
+            .unwrap()
+            .object(
+                (1, 0),
+                with_status(key(), Player::Left, status(Effect::Slow { factor: 0.5 }, 2)),
+            )
+            .unwrap()
+            .object(
+                (1, 3),
+                with_status(key(), Player::Right, status(Effect::Stun, 1)),
+            )
+            .unwrap()
+            .players(players(0))
+            .finish()
+            .unwrap();
+        let statuses = |game: &Game, position| {
+            game.cells()[position]
+                .borrow()
+                .object
+                .as_ref()
+                .map(|object| (object.object.health, object.object.statuses.len()))
+        };
+
+        // the cooldown rate is taken from the statuses before each update
+        for _ in 0..2 {
+            run(&mut game, SECOND);
+        }
+        assert_eq!(statuses(&game, (0, 0)), Some((10, 0)));
+        assert_eq!(game.players().left.resources.keys, 10);
+        assert_eq!(game.players().right.resources.keys, 10);
+
+        run(&mut game, SECOND);
+        assert_eq!(statuses(&game, (0, 0)), Some((10, 0)));
+        assert_eq!(statuses(&game, (1, 0)), Some((30, 0)));
+        assert_eq!(game.players().left.resources.keys, 20);
+        assert_eq!(game.players().right.resources.keys, 20);
+    }
+
+    #[test]
+    fn shields_absorb_damage() {
+        let mut object = owned(object::Kind::Barrier {}, 30, Player::Left);
+        object
+            .object
+            .apply_status(status(object::Effect::Shield { amount: 15 }, 5));
+        let mut cell = Cell {
+            object: Some(object),
+        };
+        let health = |cell: &Cell| cell.object.as_ref().map(|object| object.object.health);
+
+        assert!(cell.receive_damage(10).is_none());
+        assert_eq!(health(&cell), Some(30));
+        assert!(cell.receive_damage(10).is_none());
+        assert_eq!(health(&cell), Some(25));
+        assert!(cell.object.as_ref().unwrap().object.statuses.is_empty());
+    }
+
+    #[test]
+    fn casters_apply_shields_to_friendly_objects() {
+        let caster = object::Kind::Caster {
+            status: status(object::Effect::Shield { amount: 20 }, 5),
+            range: 1,
+            cooldown: Cooldown::new(SECOND),
+        };
+        let barrier = || object::Kind::Barrier {};
+        let mut game = Builder::new(settings())
+            .unwrap()
+            .object((0, 1), owned(caster, 10, Player::Left))
+            .unwrap()
+            .object((0, 0), owned(barrier(), 10, Player::Left))
+            .unwrap()
+            .object((0, 2), owned(barrier(), 10, Player::Right))
+            .unwrap()
+            .players(players(0))
+            .finish()
+            .unwrap();
+        run(&mut game, SECOND);
+        assert_eq!(
+            game.take_events(),
+            vec![GameEvent::StatusApplied {
+                position: (0, 0),
+                effect: object::Effect::Shield { amount: 20 },
+            }]
+        );
+        assert!(game.cells()[(0, 2)]
+            .borrow()
+            .object
+            .as_ref()
+            .unwrap()
+            .object
+            .statuses
+            .is_empty());
+    }
+
+    #[test]
+    fn passive_income_is_paid_every_second() {
+        let mut game = Builder::new(Settings {
+            income: Income {
+                base: 2,
+                interest_rate: 0.1,
+                interest_cap: Some(5),
+                comeback: 3,
+            },
+            ..settings()
+        })
+        .unwrap()
+        .object((0, 0), owned(object::Kind::Barrier {}, 10, Player::Left))
+        .unwrap()
+        .players(players(30))
+        .finish()
+        .unwrap();
+        game.players.right.resources.keys = 80;
+
+        // no income before a whole second has passed
+        run(&mut game, SECOND - 1);
+        assert_eq!(game.players().left.resources.keys, 30);
+
+        // base and interest, which is capped for the right player
+        run(&mut game, 1);
+        assert_eq!(game.players().left.resources.keys, 35);
+        assert_eq!(game.players().right.resources.keys, 87);
+
+        // comeback income for the player who lost more objects
+        game.hit(
+            Target::Object((0, 0)),
+            Damage {
+                amount: 10,
+                kind: damage::DamageType::Physical,
+            },
+        );
+        assert_eq!(game.players().left.losses, 1);
+        run(&mut game, SECOND);
+        assert_eq!(game.players().left.resources.keys, 43);
+        assert_eq!(game.players().right.resources.keys, 94);
+    }
+
+    #[test]
+    fn removal_only_of_own_objects() {
+        let barrier = || object::Kind::Barrier {};
+        let mut game = Builder::new(settings())
+            .unwrap()
+            .object((0, 0), owned(barrier(), 10, Player::Right))
+            .unwrap()
+            .object((0, 1), owned(barrier(), 10, Player::Left))
+            .unwrap()
+            .object((0, 2), owned(barrier(), 10, Player::Right))
+            .unwrap()
+            .players(players(0))
+            .finish()
+            .unwrap();
+
+        // an enemy object in the enemy territory
+        assert_eq!(
+            game.clear_cell(Player::Left, (0, 2)),
+            Err(ActionError::OutOfTerritory)
+        );
+        // an enemy object in the player's own territory
+        assert_eq!(
+            game.clear_cell(Player::Left, (0, 0)),
+            Err(ActionError::NotOwner)
+        );
+        assert!(game.cells()[(0, 0)].borrow().object.is_some());
+        assert!(game.cells()[(0, 2)].borrow().object.is_some());
+
+        assert_eq!(game.clear_cell(Player::Left, (0, 1)), Ok(()));
+        assert!(game.cells()[(0, 1)].borrow().object.is_none());
+    }
+
+    #[test]
+    fn sold_objects_refund_part_of_their_cost() {
+        let mut game = Builder::new(Settings {
+            removal: Removal::Sell { refund: 0.5 },
+            ..settings()
+        })
+        .unwrap()
+        .players(players(100))
+        .finish()
+        .unwrap();
+
+        // the barrier costs 20 keys
+        game.place_object(Player::Left, (0, 0), 0).unwrap();
+        game.cells()[(0, 0)].borrow_mut().receive_damage(10);
+        game.place_object(Player::Left, (0, 1), 0).unwrap();
+        assert_eq!(game.players().left.resources.keys, 60);
+
+        // 50% of the cost at 80% of the health
+        game.clear_cell(Player::Left, (0, 0)).unwrap();
+        assert_eq!(game.players().left.resources.keys, 68);
+        game.clear_cell(Player::Left, (0, 1)).unwrap();
+        assert_eq!(game.players().left.resources.keys, 78);
+
+        let refunds: Vec<_> = game
+            .take_events()
+            .into_iter()
+            .filter_map(|event| match event {
+                GameEvent::Removed { refund, .. } => Some(refund.keys),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(refunds, [8, 10]);
+    }
+
+    #[test]
+    fn refunds_are_capped_at_max_keys() {
+        let mut game = Builder::new(Settings {
+            removal: Removal::Sell { refund: 1.0 },
+            ..settings()
+        })
+        .unwrap()
+        .players(players(100))
+        .finish()
+        .unwrap();
+
+        game.place_object(Player::Left, (0, 0), 0).unwrap();
+        game.players.left.resources.keys = 95;
+        game.clear_cell(Player::Left, (0, 0)).unwrap();
+        assert_eq!(game.players().left.resources.keys, 100);
+    }
+
+    #[test]
+    fn invalid_refund_fraction() {
+        for &refund in &[-0.5, 1.5, f64::NAN] {
+            let settings = Settings {
+                removal: Removal::Sell { refund },
+                ..settings()
+            };
+            assert!(Builder::new(settings).is_err());
+        }
+    }
+
+    #[test]
+    fn upgrades_follow_the_upgrade_table() {
+        let mut players = players(100);
+        players.left.upgrades.barrier = vec![
+            object::Upgrade {
+                cost: keys(30),
+                max_health: 20,
+                ..object::Upgrade::default()
+            },
+            object::Upgrade {
+                cost: keys(50),
+                max_health: 40,
+                ..object::Upgrade::default()
+            },
+        ];
+        let barrier = || object::Kind::Barrier {};
+        let mut game = Builder::new(settings())
+            .unwrap()
+            .object((0, 0), owned(barrier(), 10, Player::Left))
+            .unwrap()
+            .object((0, 1), owned(barrier(), 10, Player::Right))
+            .unwrap()
+            .players(players)
+            .finish()
+            .unwrap();
+        let object = |game: &Game| {
+            let cells = game.cells();
+            let cell = cells[(0, 0)].borrow();
+            let object = &cell.object.as_ref().unwrap().object;
+            (object.level, object.health, object.max_health)
+        };
+
+        assert_eq!(
+            game.upgrade_object(Player::Left, (1, 0)),
+            Err(ActionError::Empty)
+        );
+        assert_eq!(
+            game.upgrade_object(Player::Left, (0, 1)),
+            Err(ActionError::NotOwner)
+        );
+        assert_eq!(
+            game.upgrade_object(Player::Left, (0, 2)),
+            Err(ActionError::OutOfTerritory)
+        );
+
+        assert_eq!(game.upgrade_object(Player::Left, (0, 0)), Ok(()));
+        assert_eq!(object(&game), (1, 30, 30));
+        assert_eq!(game.players().left.resources.keys, 70);
+
+        assert_eq!(game.upgrade_object(Player::Left, (0, 0)), Ok(()));
+        assert_eq!(object(&game), (2, 70, 70));
+        assert_eq!(game.players().left.resources.keys, 20);
+
+        assert_eq!(
+            game.upgrade_object(Player::Left, (0, 0)),
+            Err(ActionError::MaxLevel)
+        );
+        assert_eq!(
+            game.take_events().last(),
+            Some(&GameEvent::Upgraded {
+                player: Player::Left,
+                position: (0, 0),
+                level: 2,
+                cost: keys(50),
+            })
+        );
+    }
+
+    #[test]
+    fn objects_move_within_the_territory() {
+        let barrier = || object::Kind::Barrier {};
+        let mut movable = owned(barrier(), 10, Player::Left);
+        movable.object.relocation = Some(object::Relocation {
+            cost: keys(15),
+            cooldown: Cooldown::new(5 * SECOND),
+        });
+        let mut game = Builder::new(settings())
+            .unwrap()
+            .object((0, 0), movable)
+            .unwrap()
+            .object((0, 1), owned(barrier(), 10, Player::Left))
+            .unwrap()
+            .object((1, 0), owned(barrier(), 10, Player::Right))
+            .unwrap()
+            .players(players(20))
+            .finish()
+            .unwrap();
+
+        let cases = [
+            ((0, 0), (0, 2), ActionError::OutOfTerritory),
+            ((0, 0), (0, 0), ActionError::SamePosition),
+            ((0, 0), (0, 1), ActionError::Occupied),
+            ((2, 0), (2, 1), ActionError::Empty),
+            ((1, 0), (2, 1), ActionError::NotOwner),
+            ((0, 1), (2, 1), ActionError::Immovable),
+            (
+                (0, 0),
+                (2, 1),
+                ActionError::OnCooldown {
+                    remaining: 5 * SECOND,
+                },
+            ),
+        ];
+        for &(from, to, error) in &cases {
+            assert_eq!(game.move_object(Player::Left, from, to), Err(error));
+        }
+        run(&mut game, 5 * SECOND);
+
+        assert_eq!(game.move_object(Player::Left, (0, 0), (2, 1)), Ok(()));
+        assert!(game.cells()[(0, 0)].borrow().object.is_none());
+        assert!(game.cells()[(2, 1)].borrow().object.is_some());
+        assert_eq!(game.players().left.resources.keys, 5);
+        assert_eq!(
+            game.take_events().last(),
+            Some(&GameEvent::Moved {
+                player: Player::Left,
+                from: (0, 0),
+                to: (2, 1),
+                cost: keys(15),
+            })
+        );
+
+        run(&mut game, 5 * SECOND);
+        assert_eq!(
+            game.move_object(Player::Left, (2, 1), (2, 0)),
+            Err(ActionError::InsufficientResources {
+                resource: Resource::Keys,
+                needed: 15,
+                have: 5
+            })
+        );
+    }
+
+    /// The state that a rejected action must not change.
+    #[derive(Debug, PartialEq)]
+    struct State {
+        keys: Vec<u32>,
+        cooldowns: Vec<(bool, u32)>,
+        cells: Vec<Option<Player>>,
+    }
+
+    fn state(game: &Game) -> State {
+        let data = [&game.players().left, &game.players().right];
+        State {
+            keys: data.iter().map(|data| data.resources.keys).collect(),
+            cooldowns: data
+                .iter()
+                .flat_map(|data| &data.placements)
+                .map(|placement| (placement.cooldown.is_over(), placement.cooldown.remaining()))
+                .collect(),
+            cells: game
+                .cells()
+                .iter()
+                .map(|cell| cell.borrow().object.as_ref().map(|object| object.owner))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn rejected_placements_change_nothing() {
+        let mut game = Builder::new(settings())
+            .unwrap()
+            .object((0, 0), owned(object::Kind::Barrier {}, 10, Player::Left))
+            .unwrap()
+            .players(players(30))
+            .finish()
+            .unwrap();
+        let before = state(&game);
+
+        let cases = [
+            ((3, 0), 0, PlacementError::InvalidPosition),
+            ((1, 2), 0, PlacementError::OutOfTerritory),
+            ((0, 0), 0, PlacementError::Occupied),
+            ((1, 0), 2, PlacementError::InvalidIndex),
+            (
+                (1, 0),
+                1,
+                PlacementError::OnCooldown {
+                    remaining: 10 * SECOND,
+                },
+            ),
+        ];
+        for &(position, index, error) in &cases {
+            assert_eq!(game.place_object(Player::Left, position, index), Err(error));
+            assert_eq!(state(&game), before);
+        }
+
+        assert_eq!(game.place_object(Player::Left, (1, 0), 0), Ok(()));
+        assert_eq!(game.players().left.resources.keys, 10);
+        let before = state(&game);
+        assert_eq!(
+            game.place_object(Player::Left, (1, 1), 0),
+            Err(PlacementError::InsufficientKeys {
+                needed: 20,
+                have: 10
+            })
+        );
+        assert_eq!(state(&game), before);
+    }
+
+    #[test]
+    fn rejected_removals_change_nothing() {
+        let mut game = Builder::new(settings())
+            .unwrap()
+            .object((0, 0), owned(object::Kind::Barrier {}, 10, Player::Right))
+            .unwrap()
+            .players(players(30))
+            .finish()
+            .unwrap();
+        let before = state(&game);
+
+        let cases = [
+            ((0, 4), ActionError::InvalidPosition),
+            ((0, 3), ActionError::OutOfTerritory),
+            ((0, 0), ActionError::NotOwner),
+        ];
+        for &(position, error) in &cases {
+            assert_eq!(game.clear_cell(Player::Left, position), Err(error));
+            assert_eq!(state(&game), before);
         }
     }
 }
@@ -0,0 +1,91 @@
+//! Command line handling shared by the interactive binaries.
+//!
+//! Both the windowed game and the terminal game accept the same arguments:
+//!
+//! - no argument plays with the default catalog;
+//! - `CATALOG` plays with the specified catalog file;
+//! - `--replay REPLAY` plays back the specified replay file;
+//! - `--computer left` or `--computer right` lets the computer
+//!   control the specified player.
+//!
+//! Only the key bindings differ between the binaries.
+
+use crate::controller::{self, KeyBinding, Speed};
+use crate::{ai, Catalog, Controller, Player, Players, Replay};
+use anyhow::{anyhow, Result};
+use piston::Button;
+use std::ffi::OsString;
+
+/// The arguments accepted by the interactive binaries, for usage messages.
+pub const ARGUMENTS: &str = "[--computer left|right]... [CATALOG | --replay REPLAY]";
+
+/// The buttons used to control the game.
+#[derive(Clone, Debug)]
+pub struct Bindings {
+    /// The key binding for players.
+    pub key_binding: Players<KeyBinding>,
+
+    /// The key for pausing and resuming the game.
+    pub pause: Button,
+
+    /// The keys for changing the game speed.
+    pub speeds: Vec<(Button, Speed)>,
+}
+
+/// Creates the game controller according to the command line arguments,
+/// not including the program name.
+///
+/// `program` is the program name shown in usage messages.
+pub fn create_controller<I>(program: &str, args: I, bindings: Bindings) -> Result<Controller>
+where
+    I: IntoIterator<Item = OsString>,
+{
+    let usage = || anyhow!("usage: {program} {ARGUMENTS}");
+
+    let mut computer_players = Players::default();
+    let mut catalog_path = None;
+    let mut replay_path = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--replay" {
+            replay_path = Some(args.next().ok_or_else(usage)?);
+        } else if arg == "--computer" {
+            let player = match args.next().as_ref().and_then(|arg| arg.to_str()) {
+                Some("left") => Player::Left,
+                Some("right") => Player::Right,
+                _ => return Err(usage()),
+            };
+            computer_players[player] = Some(ai::Builtin::Greedy);
+        } else if catalog_path.is_none() {
+            catalog_path = Some(arg);
+        } else {
+            return Err(usage());
+        }
+    }
+
+    let settings = |selected_cells| controller::Settings {
+        key_binding: bindings.key_binding,
+        selected_cells,
+        computer_players,
+        pause: bindings.pause,
+        speeds: bindings.speeds,
+    };
+
+    match (replay_path, catalog_path) {
+        (Some(path), None) => {
+            let replay = Replay::load(path)?;
+            Controller::from_replay(settings(replay.selected_cells), replay)
+        }
+        (None, catalog_path) => {
+            let catalog = match catalog_path {
+                Some(path) => Catalog::load(path)?,
+                None => Catalog::builtin()?,
+            };
+            let game = catalog.game()?;
+            let selected_cells = controller::initial_selected_cells(game.settings());
+            Controller::new(settings(selected_cells), game)
+        }
+        (Some(_), Some(_)) => Err(usage()),
+    }
+}
@@ -7,16 +7,32 @@
 
 //! A simple battle game.
 
+pub mod ai;
+pub mod catalog;
 pub mod controller;
 pub mod cooldown;
+pub mod damage;
 pub mod game;
+pub mod launch;
 pub mod object;
 pub mod player;
+pub mod projectile;
 pub mod renderer;
+pub mod replay;
+pub mod resource;
+pub mod snapshot;
+pub mod text_renderer;
+pub mod tick;
 
+pub use catalog::Catalog;
 pub use controller::Controller;
 pub use cooldown::Cooldown;
 pub use game::Game;
 pub use object::Object;
 pub use player::{Player, Players};
+pub use projectile::Projectile;
 pub use renderer::Renderer;
+pub use replay::Replay;
+pub use resource::{Resource, Resources};
+pub use snapshot::Snapshot;
+pub use text_renderer::TextRenderer;
@@ -9,46 +9,76 @@ use glutin_window::GlutinWindow;
 use graphics::color::{BLACK, WHITE};
 use graphics::line;
 use graphics::rectangle;
+use graphics::text::Text;
 use lockwars::{
-    controller, game, object, player, renderer, Controller, Cooldown, Game, Object, Player,
-    Players, Renderer,
+    controller, game, launch, renderer, Controller, Player, Players, Renderer, Resources, Snapshot,
 };
-use opengl_graphics::{GlGraphics, OpenGL};
+use opengl_graphics::{GlGraphics, GlyphCache, OpenGL, TextureSettings};
 use piston::{
-    Button, ButtonEvent, EventSettings, Events, Key, RenderEvent, UpdateEvent, WindowSettings,
+    AdvancedWindow, Button, ButtonEvent, ButtonState, EventSettings, Events, Key, RenderEvent,
+    UpdateEvent, WindowSettings,
 };
-use std::time::Duration;
 
 const WINDOW_TITLE: &str = "Lockwars";
 const WINDOW_SIZE: (u32, u32) = (1280, 720);
 
+const QUICK_SAVE_PATH: &str = "quicksave.ron";
+const QUICK_SAVE_BUTTON: Button = Button::Keyboard(Key::F5);
+const QUICK_LOAD_BUTTON: Button = Button::Keyboard(Key::F9);
+
+const REPLAY_PATH: &str = "replay.ron";
+
+/// The font used to draw text.
+const FONT: &[u8] = include_bytes!("../assets/FiraSans-Regular.ttf");
+
 fn main() -> Result<()> {
     let opengl = OpenGL::V3_2;
 
     let mut window = create_window(opengl)?;
     let mut gl = GlGraphics::new(opengl);
 
-    let game = create_game()?;
-    let mut controller = create_controller(game)?;
+    let mut controller =
+        launch::create_controller("lockwars", std::env::args_os().skip(1), bindings())?;
     let renderer = create_renderer()?;
+    let mut glyphs = GlyphCache::from_bytes(FONT, (), TextureSettings::new())
+        .map_err(|()| anyhow!("cannot load font"))?;
 
     let event_settings = EventSettings::new();
     let mut events = Events::new(event_settings);
 
     while let Some(event) = events.next(&mut window) {
         if let Some(args) = event.button_args() {
+            if args.state == ButtonState::Release {
+                if args.button == QUICK_SAVE_BUTTON {
+                    if let Err(error) = quick_save(&controller) {
+                        eprintln!("{error:#}");
+                    }
+                } else if args.button == QUICK_LOAD_BUTTON {
+                    if let Err(error) = quick_load(&mut controller) {
+                        eprintln!("{error:#}");
+                    }
+                    show_outcome(&mut window, controller.game().outcome());
+                }
+            }
             controller.button_event(args)?;
         }
         if let Some(args) = event.render_args() {
             gl.draw(args.viewport(), |context, g| {
-                renderer.draw(&controller, &context, g)
+                renderer.draw(&controller, &mut glyphs, &context, g)
             })?;
         }
         if let Some(args) = event.update_args() {
-            controller.update_event(args)?;
+            if controller.game().outcome() == game::Outcome::Ongoing {
+                controller.update_event(args)?;
+                show_outcome(&mut window, controller.game().outcome());
+            }
         }
     }
 
+    if !controller.is_replaying() {
+        controller.replay().save(REPLAY_PATH)?;
+    }
+
     Ok(())
 }
 
@@ -61,101 +91,33 @@ fn create_window(opengl: OpenGL) -> Result<GlutinWindow> {
     GlutinWindow::new(&window_settings).map_err(|_| anyhow!("cannot create window"))
 }
 
-fn create_game() -> Result<Game> {
-    use object::{Kind, Owned};
-
-    let game_settings = game::Settings {
-        n_columns: 6,
-        n_rows: 7,
-        base_span: 2..5,
-        max_keys: 1000,
-    };
-    let players = Players {
-        left: player_data(),
-        right: player_data(),
-    };
+fn quick_save(controller: &Controller) -> Result<()> {
+    Snapshot::of(controller.game()).save(QUICK_SAVE_PATH)
+}
 
-    game::Builder::new(game_settings)?
-        .object(
-            (3, 0),
-            Owned {
-                object: Object {
-                    kind: Kind::Key {
-                        generation: 10,
-                        cooldown: Cooldown::new(Duration::from_secs(1)),
-                    },
-                    health: u32::MAX,
-                    max_health: u32::MAX,
-                },
-                owner: Player::Left,
-            },
-        )?
-        .object(
-            (3, 11),
-            Owned {
-                object: Object {
-                    kind: Kind::Key {
-                        generation: 10,
-                        cooldown: Cooldown::new(Duration::from_secs(1)),
-                    },
-                    health: u32::MAX,
-                    max_health: u32::MAX,
-                },
-                owner: Player::Right,
-            },
-        )?
-        .players(players)
-        .finish()
+fn quick_load(controller: &mut Controller) -> Result<()> {
+    let game = Snapshot::load(QUICK_SAVE_PATH)?.restore()?;
+    controller.replace_game(game)
 }
 
-fn player_data() -> player::Data {
-    use object::Kind;
-    use player::Placement;
-
-    player::Data {
-        keys: 200,
-        placements: vec![
-            Placement {
-                cooldown: Cooldown::new(Duration::from_secs(1)),
-                cost: 20,
-                generate_object: Box::new(|| Object {
-                    kind: Kind::Key {
-                        generation: 10,
-                        cooldown: Cooldown::new(Duration::from_secs(1)),
-                    },
-                    health: 100,
-                    max_health: 100,
-                }),
-            },
-            Placement {
-                cooldown: Cooldown::new(Duration::from_secs(1)),
-                cost: 40,
-                generate_object: Box::new(|| Object {
-                    kind: Kind::Fire {
-                        damage: 20,
-                        cooldown: Cooldown::new(Duration::from_secs(1)),
-                    },
-                    health: 100,
-                    max_health: 100,
-                }),
-            },
-            Placement {
-                cooldown: Cooldown::new(Duration::from_secs(1)),
-                cost: 20,
-                generate_object: Box::new(|| Object {
-                    kind: Kind::Barrier {},
-                    health: 3600,
-                    max_health: 3600,
-                }),
-            },
-        ],
-    }
+fn show_outcome(window: &mut GlutinWindow, outcome: game::Outcome) {
+    let result = match outcome {
+        game::Outcome::Ongoing => {
+            window.set_title(WINDOW_TITLE.to_owned());
+            return;
+        }
+        game::Outcome::Won(Player::Left) => "left player wins",
+        game::Outcome::Won(Player::Right) => "right player wins",
+        game::Outcome::Draw => "draw",
+    };
+    window.set_title(format!("{WINDOW_TITLE} - {result}"));
 }
 
-fn create_controller(game: Game) -> Result<Controller> {
+/// Creates the button bindings.
+fn bindings() -> launch::Bindings {
     use controller::KeyBinding;
 
-    let controller_settings = controller::Settings {
+    launch::Bindings {
         key_binding: Players {
             left: KeyBinding {
                 up: Button::Keyboard(Key::W),
@@ -163,11 +125,25 @@ fn create_controller(game: Game) -> Result<Controller> {
                 left: Button::Keyboard(Key::A),
                 right: Button::Keyboard(Key::D),
                 remove: Button::Keyboard(Key::G),
-                place: [Key::T, Key::Y, Key::U]
-                    .iter()
-                    .copied()
-                    .map(Button::Keyboard)
-                    .collect(),
+                upgrade: Button::Keyboard(Key::F),
+                relocate: Button::Keyboard(Key::R),
+                place: [
+                    Key::T,
+                    Key::Y,
+                    Key::U,
+                    Key::I,
+                    Key::O,
+                    Key::P,
+                    Key::LeftBracket,
+                    Key::RightBracket,
+                    Key::Backslash,
+                    Key::Semicolon,
+                    Key::Quote,
+                ]
+                .iter()
+                .copied()
+                .map(Button::Keyboard)
+                .collect(),
             },
             right: KeyBinding {
                 up: Button::Keyboard(Key::Up),
@@ -175,20 +151,35 @@ fn create_controller(game: Game) -> Result<Controller> {
                 left: Button::Keyboard(Key::Left),
                 right: Button::Keyboard(Key::Right),
                 remove: Button::Keyboard(Key::NumPad0),
-                place: [Key::NumPad1, Key::NumPad2, Key::NumPad3]
-                    .iter()
-                    .copied()
-                    .map(Button::Keyboard)
-                    .collect(),
+                upgrade: Button::Keyboard(Key::NumPadPlus),
+                relocate: Button::Keyboard(Key::NumPadPeriod),
+                place: [
+                    Key::NumPad1,
+                    Key::NumPad2,
+                    Key::NumPad3,
+                    Key::NumPad4,
+                    Key::NumPad5,
+                    Key::NumPad6,
+                    Key::NumPad7,
+                    Key::NumPad8,
+                    Key::NumPad9,
+                    Key::NumPadDivide,
+                    Key::NumPadMultiply,
+                ]
+                .iter()
+                .copied()
+                .map(Button::Keyboard)
+                .collect(),
             },
         },
-        selected_cells: Players {
-            left: (3, 0),
-            right: (3, 11),
-        },
-    };
-
-    Controller::new(controller_settings, game)
+        pause: Button::Keyboard(Key::Space),
+        speeds: vec![
+            (Button::Keyboard(Key::F1), controller::Speed::Half),
+            (Button::Keyboard(Key::F2), controller::Speed::Normal),
+            (Button::Keyboard(Key::F3), controller::Speed::Double),
+            (Button::Keyboard(Key::F4), controller::Speed::Quadruple),
+        ],
+    }
 }
 
 fn create_renderer() -> Result<Renderer> {
@@ -205,20 +196,38 @@ fn create_renderer() -> Result<Renderer> {
             color: WHITE,
             radius: 1.0,
         },
+        base_health_color: [0.2, 0.2, 0.4, 1.0],
         object_percentage: 0.6,
         object_outline_color: [0.8, 0.4, 0.4, 1.0],
         object_outline_radius: 1.0,
+        cooldown_ring_percentage: 1.2,
+        cooldown_ring_color: [0.4, 0.4, 0.8, 1.0],
+        cooldown_ring_radius: 1.0,
+        level_color: [1.0, 0.85, 0.2, 1.0],
+        status_color: [0.4, 0.8, 1.0, 1.0],
+        projectile_percentage: 0.15,
+        projectile_color: [1.0, 0.6, 0.2, 1.0],
         selected_cell_color: [0.0, 0.2, 0.0, 1.0],
-        key_bar_border: rectangle::Border {
+        carried_cell_color: [0.2, 0.2, 0.0, 1.0],
+        resource_bar_border: rectangle::Border {
             color: WHITE,
             radius: 1.0,
         },
-        key_bar_division_line: line::Line::new(WHITE, 1.0),
-        key_bar_color: WHITE,
+        resource_bar_division_line: line::Line::new(WHITE, 1.0),
+        resource_bar_colors: Resources {
+            keys: WHITE,
+            energy: [0.4, 0.8, 1.0, 1.0],
+        },
         health_bar_height_percentage: 0.7,
         health_bar_width_percentage: 0.04,
         health_bar_background: [0.4, 0.2, 0.2, 1.0],
         health_bar_color: [0.8, 0.4, 0.4, 1.0],
+        text_color: WHITE,
+        palette_cooldown_color: [0.8, 0.4, 0.4, 1.0],
+        unaffordable_color: [0.0, 0.0, 0.0, 0.6],
+        action_error_color: [1.0, 0.4, 0.4, 1.0],
+        pause_overlay_color: [0.0, 0.0, 0.0, 0.6],
+        pause_text: Text::new_color(WHITE, 48),
     };
 
     Renderer::new(renderer_settings)
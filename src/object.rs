@@ -1,9 +1,15 @@
 //! The objects in the game.
 
-use crate::{Cooldown, Player};
+use crate::damage::{self, Armor, Damage, DamageType};
+use crate::projectile::{self, Targeting};
+use crate::tick::TICKS_PER_SECOND;
+use crate::{Cooldown, Player, Resource, Resources};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
 /// An object.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Object {
     /// The kind of the object.
     ///
@@ -15,17 +21,311 @@ pub struct Object {
 
     /// The maximum health of the object.
     pub max_health: u32,
+
+    /// The armor of the object.
+    #[serde(default)]
+    pub armor: Armor,
+
+    /// The number of upgrades applied to the object.
+    #[serde(default)]
+    pub level: u32,
+
+    /// The resources paid to place the object.
+    #[serde(default)]
+    pub cost: Resources<u32>,
+
+    /// The cost and cooldown of moving the object to another cell,
+    /// or `None` if the object cannot be moved.
+    #[serde(default)]
+    pub relocation: Option<Relocation>,
+
+    /// The status effects on the object.
+    #[serde(default)]
+    pub statuses: Vec<Status>,
+}
+
+impl Object {
+    /// Checks that the object can be simulated:
+    /// the maximum health must be positive,
+    /// fire objects must fire projectiles with a valid speed
+    /// and a valid targeting mode,
+    /// and all status effects must be valid.
+    pub fn check(&self) -> Result<()> {
+        if self.max_health == 0 {
+            return Err(anyhow!("object must have positive maximum health"));
+        }
+        match &self.kind {
+            Kind::Fire {
+                speed,
+                targeting,
+                effect,
+                ..
+            } => {
+                projectile::check_speed(*speed)?;
+                targeting.check()?;
+                if let Some(status) = effect {
+                    status.effect.check()?;
+                }
+            }
+            Kind::Caster { status, .. } => status.effect.check()?,
+            Kind::Key { .. } | Kind::Barrier {} | Kind::Healer { .. } => {}
+        }
+        for status in &self.statuses {
+            status.effect.check()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the resources refunded when the object is sold,
+    /// given the fraction of the cost to be refunded at full health.
+    ///
+    /// The refund is scaled by the remaining health of the object.
+    pub fn refund(&self, fraction: f64) -> Resources<u32> {
+        self.cost
+            .scale(fraction * f64::from(self.health) / f64::from(self.max_health))
+    }
+
+    /// Restores the specified amount of health,
+    /// up to the maximum health.
+    ///
+    /// Returns the amount of health actually restored.
+    pub fn heal(&mut self, amount: u32) -> u32 {
+        let old_health = self.health;
+        self.health = self.health.saturating_add(amount).min(self.max_health);
+        self.health - old_health
+    }
+
+    /// Returns the cooldown of the object, if any.
+    pub fn cooldown_mut(&mut self) -> Option<&mut Cooldown> {
+        match &mut self.kind {
+            Kind::Key { cooldown, .. }
+            | Kind::Fire { cooldown, .. }
+            | Kind::Healer { cooldown, .. }
+            | Kind::Caster { cooldown, .. } => Some(cooldown),
+            Kind::Barrier {} => None,
+        }
+    }
+
+    /// Applies a status effect to the object.
+    ///
+    /// A status with the same kind of effect is replaced,
+    /// so that reapplying an effect refreshes it instead of stacking.
+    pub fn apply_status(&mut self, status: Status) {
+        use std::mem::discriminant;
+
+        let effect = discriminant(&status.effect);
+        self.statuses
+            .retain(|other| discriminant(&other.effect) != effect);
+        self.statuses.push(status);
+    }
+
+    /// Absorbs the specified amount of damage with the shields of the object,
+    /// and returns the damage that is not absorbed.
+    ///
+    /// Depleted shields are removed.
+    pub fn absorb(&mut self, mut damage: u32) -> u32 {
+        for status in &mut self.statuses {
+            if let Effect::Shield { amount } = &mut status.effect {
+                let absorbed = damage.min(*amount);
+                *amount -= absorbed;
+                damage -= absorbed;
+            }
+        }
+        self.statuses
+            .retain(|status| status.effect != Effect::Shield { amount: 0 });
+        damage
+    }
+
+    /// Returns the number of ticks by which the cooldown of the object
+    /// advances in the next tick, as affected by slow effects.
+    ///
+    /// A slowed cooldown advances on a fraction of the ticks
+    /// given by the slow factor, spread evenly over the duration of the effect.
+    pub fn cooldown_step(&self) -> u32 {
+        self.statuses
+            .iter()
+            .map(|status| match status.effect {
+                Effect::Slow { factor } => {
+                    let progress = |elapsed: u32| {
+                        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                        let progress = (f64::from(elapsed) * factor.clamp(0.0, 1.0)) as u32;
+                        progress
+                    };
+                    progress(status.elapsed + 1) - progress(status.elapsed)
+                }
+                Effect::Stun | Effect::Burn { .. } | Effect::Shield { .. } => 1,
+            })
+            .fold(1, u32::min)
+    }
+
+    /// Applies the upgrade and raises the level of the object.
+    ///
+    /// Bonuses that do not apply to the kind of the object are ignored.
+    /// The health is raised together with the maximum health.
+    pub fn apply_upgrade(&mut self, upgrade: &Upgrade) {
+        match &mut self.kind {
+            Kind::Key { generation, .. } => {
+                *generation = generation.saturating_add(upgrade.generation);
+            }
+            Kind::Fire { damage, .. } => *damage = damage.saturating_add(upgrade.damage),
+            Kind::Healer { amount, .. } => *amount = amount.saturating_add(upgrade.healing),
+            Kind::Barrier {} | Kind::Caster { .. } => {}
+        }
+
+        if let Some(cooldown) = self.cooldown_mut() {
+            let duration = cooldown
+                .duration()
+                .saturating_sub(upgrade.cooldown_reduction);
+            cooldown.set_duration(duration);
+        }
+
+        self.max_health = self.max_health.saturating_add(upgrade.max_health);
+        self.health = self.health.saturating_add(upgrade.max_health);
+        self.level += 1;
+    }
+
+    /// Advances the status effects of the object by one tick,
+    /// together with its cooldowns unless `cooldowns_frozen` is true,
+    /// and returns the burn damage taken in that tick.
+    ///
+    /// Burn damage is fire damage mitigated by the armor of the object,
+    /// with the damage per second counting as one hit.
+    /// The cooldown of the object advances by [`cooldown_step`] ticks,
+    /// and is paused while the object is stunned.
+    /// Expired status effects are removed.
+    ///
+    /// [`cooldown_step`]: #method.cooldown_step
+    pub fn tick(&mut self, cooldowns_frozen: bool) -> u32 {
+        let stunned = self
+            .statuses
+            .iter()
+            .any(|status| status.effect == Effect::Stun);
+        let step = self.cooldown_step();
+        if let Some(cooldown) = self.cooldown_mut() {
+            if stunned {
+                cooldown.pause();
+            } else {
+                cooldown.resume();
+            }
+            if !cooldowns_frozen {
+                cooldown.advance(step);
+            }
+        }
+        if !cooldowns_frozen {
+            if let Some(relocation) = &mut self.relocation {
+                relocation.cooldown.advance(1);
+            }
+        }
+
+        let mut burn = 0;
+        for status in &mut self.statuses {
+            let before = status.elapsed;
+            status.elapsed = (status.elapsed + 1).min(status.duration);
+
+            if let Effect::Burn { damage_per_second } = status.effect {
+                let damage_per_second = damage::mitigate(
+                    Damage {
+                        amount: damage_per_second,
+                        kind: DamageType::Fire,
+                    },
+                    &self.armor,
+                );
+                let burnt = |elapsed: u32| {
+                    u64::from(damage_per_second) * u64::from(elapsed) / u64::from(TICKS_PER_SECOND)
+                };
+                let damage = burnt(status.elapsed) - burnt(before);
+                burn += u32::try_from(damage).unwrap_or(u32::MAX);
+            }
+        }
+        self.statuses
+            .retain(|status| status.elapsed < status.duration);
+
+        burn
+    }
+}
+
+/// A status effect on an object, with its duration.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Status {
+    /// The effect.
+    pub effect: Effect,
+
+    /// The total duration of the effect, in ticks.
+    pub duration: u32,
+
+    /// The number of ticks for which the effect has been active.
+    #[serde(default)]
+    pub elapsed: u32,
+}
+
+/// A status effect.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Effect {
+    /// Deals damage over time.
+    Burn {
+        /// The amount of damage dealt per second.
+        damage_per_second: u32,
+    },
+    /// Stretches the cooldown of the object.
+    Slow {
+        /// The rate at which the cooldown advances, between 0 and 1.
+        factor: f64,
+    },
+    /// Absorbs damage before the health of the object is reduced.
+    Shield {
+        /// The amount of damage left to be absorbed.
+        amount: u32,
+    },
+    /// Pauses the cooldown of the object.
+    Stun,
+}
+
+impl Effect {
+    /// Checks that the slow factor, if any, is between 0 and 1.
+    pub fn check(self) -> Result<()> {
+        match self {
+            Effect::Slow { factor } if !(0.0..=1.0).contains(&factor) => {
+                Err(anyhow!("{factor} is not a valid slow factor"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns true if the effect hinders the object,
+    /// and is therefore applied to enemy objects.
+    pub fn is_harmful(self) -> bool {
+        match self {
+            Effect::Burn { .. } | Effect::Slow { .. } | Effect::Stun => true,
+            Effect::Shield { .. } => false,
+        }
+    }
+}
+
+/// The cost and cooldown of moving an object to another cell.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Relocation {
+    /// The resources paid for each move.
+    pub cost: Resources<u32>,
+
+    /// The cooldown between moves,
+    /// which starts when the object is placed.
+    pub cooldown: Cooldown,
 }
 
 /// The kind of an object.
 ///
 /// Contains kind-specific object information.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub enum Kind {
     /// A key object.
+    ///
+    /// Generates keys, or another resource.
     Key {
-        /// The amount of keys generated by the key object.
+        /// The amount of the resource generated by the key object.
         generation: u32,
+        /// The resource generated by the key object.
+        #[serde(default)]
+        resource: Resource,
         /// The cooldown for key generation.
         cooldown: Cooldown,
     },
@@ -33,15 +333,115 @@ pub enum Kind {
     Fire {
         /// The amount of damage dealt by the fire object.
         damage: u32,
+        /// The type of damage dealt by the fire object.
+        #[serde(default)]
+        damage_type: DamageType,
+        /// The speed of the projectiles, in cells per second.
+        speed: f64,
+        /// The targeting mode of the projectiles.
+        #[serde(default)]
+        targeting: Targeting,
+        /// The status effect applied to objects directly hit by the projectiles.
+        #[serde(default)]
+        effect: Option<Status>,
         /// The cooldown for attack.
         cooldown: Cooldown,
     },
     /// A barrier object.
     Barrier {},
+    /// A healer object.
+    ///
+    /// Restores the health of nearby friendly objects.
+    Healer {
+        /// The amount of health restored to each object.
+        amount: u32,
+        /// The maximum distance to the healed objects,
+        /// measured in steps between adjacent cells.
+        range: usize,
+        /// The cooldown for healing.
+        cooldown: Cooldown,
+    },
+    /// A caster object.
+    ///
+    /// Applies a status effect to nearby objects:
+    /// harmful effects to enemy objects,
+    /// and other effects to friendly objects.
+    Caster {
+        /// The status applied to each object.
+        status: Status,
+        /// The maximum distance to the affected objects,
+        /// measured in steps between adjacent cells.
+        range: usize,
+        /// The cooldown for casting.
+        cooldown: Cooldown,
+    },
+}
+
+/// An upgrade of an object.
+///
+/// The bonuses are added to the corresponding properties of the object.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Upgrade {
+    /// The cost of the upgrade.
+    pub cost: Resources<u32>,
+    /// The bonus to the key generation of key objects.
+    pub generation: u32,
+    /// The bonus to the damage of fire objects.
+    pub damage: u32,
+    /// The bonus to the healing amount of healer objects.
+    pub healing: u32,
+    /// The number of ticks by which the cooldown is shortened.
+    pub cooldown_reduction: u32,
+    /// The bonus to the maximum health.
+    pub max_health: u32,
+}
+
+/// The upgrade tables for each kind of object.
+///
+/// An object at level `n` is upgraded with the `n`-th entry
+/// of the table for its kind.
+/// Objects whose level equals the length of the table
+/// cannot be upgraded further.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Upgrades {
+    /// The upgrade table for key objects.
+    pub key: Vec<Upgrade>,
+    /// The upgrade table for fire objects.
+    pub fire: Vec<Upgrade>,
+    /// The upgrade table for barrier objects.
+    pub barrier: Vec<Upgrade>,
+    /// The upgrade table for healer objects.
+    pub healer: Vec<Upgrade>,
+    /// The upgrade table for caster objects.
+    pub caster: Vec<Upgrade>,
+}
+
+impl Upgrades {
+    /// Returns the upgrade table for the specified kind of object.
+    pub fn table(&self, kind: &Kind) -> &[Upgrade] {
+        match kind {
+            Kind::Key { .. } => &self.key,
+            Kind::Fire { .. } => &self.fire,
+            Kind::Barrier {} => &self.barrier,
+            Kind::Healer { .. } => &self.healer,
+            Kind::Caster { .. } => &self.caster,
+        }
+    }
+
+    /// Returns the next upgrade for the specified object,
+    /// or `None` if the object is at the maximum level.
+    pub fn next(&self, object: &Object) -> Option<&Upgrade> {
+        use std::convert::TryFrom;
+
+        let level = usize::try_from(object.level).ok()?;
+        self.table(&object.kind).get(level)
+    }
 }
 
 /// An object owned by a player.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Owned {
     /// The object.
     pub object: Object,
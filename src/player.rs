@@ -1,10 +1,11 @@
 //! The players.
 
-use crate::{Cooldown, Object};
-use std::fmt;
+use crate::object::Upgrades;
+use crate::{Cooldown, Object, Resources};
+use serde::{Deserialize, Serialize};
 
 /// A player.
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub enum Player {
     /// The left player.
     Left,
@@ -14,6 +15,7 @@ pub enum Player {
 
 impl Player {
     /// Returns the opposite player.
+    #[must_use]
     pub fn toggle(self) -> Player {
         match self {
             Player::Left => Player::Right,
@@ -23,7 +25,7 @@ impl Player {
 }
 
 /// A container that holds the same data for both players.
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
 pub struct Players<T> {
     /// The data associated with the left player.
     pub left: T,
@@ -52,37 +54,39 @@ impl<T> std::ops::IndexMut<Player> for Players<T> {
 }
 
 /// The player data.
-#[derive(Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Data {
-    /// The amount of keys the player owns.
-    pub keys: u32,
+    /// The amount of each resource the player owns.
+    pub resources: Resources<u32>,
     /// Placements.
     pub placements: Vec<Placement>,
+    /// The upgrade tables.
+    #[serde(default)]
+    pub upgrades: Upgrades,
+    /// The number of objects of the player destroyed so far.
+    #[serde(default)]
+    pub losses: u32,
 }
 
 /// A placement.
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Placement {
     /// The cooldown of the placement.
     pub cooldown: Cooldown,
     /// The cost of the placement.
-    pub cost: u32,
-    /// Generates the object to be placed.
-    pub generate_object: Box<dyn Fn() -> Object>,
+    pub cost: Resources<u32>,
+    /// The template of the object to be placed.
+    pub object: Object,
 }
 
 impl Placement {
     /// Generates the object to be placed.
+    ///
+    /// The object remembers the cost of the placement.
     pub fn generate_object(&self) -> Object {
-        (self.generate_object)()
-    }
-}
-
-impl fmt::Debug for Placement {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("Placement")
-            .field("cooldown", &self.cooldown)
-            .field("cost", &self.cost)
-            .field("object", &self.generate_object())
-            .finish()
+        Object {
+            cost: self.cost,
+            ..self.object.clone()
+        }
     }
 }
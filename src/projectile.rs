@@ -0,0 +1,169 @@
+//! Projectiles fired by fire objects.
+//!
+//! A projectile travels along its row towards the enemy base
+//! and hits enemy objects according to its [`Targeting`] mode.
+//! Objects placed in its path before it arrives intercept it.
+//! A projectile that crosses the whole enemy territory
+//! damages the enemy base if its row is within the base span,
+//! and disappears otherwise.
+//!
+//! [`Targeting`]: enum.Targeting.html
+
+use crate::damage::Damage;
+use crate::object::Status;
+use crate::tick::TICKS_PER_SECOND;
+use crate::Player;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// A projectile.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Projectile {
+    /// The player who fired the projectile.
+    pub owner: Player,
+
+    /// The row the projectile travels along.
+    pub row: usize,
+
+    /// The horizontal position of the projectile, in cells.
+    ///
+    /// The projectile is in the cell with column `column.floor()`.
+    pub column: f64,
+
+    /// The speed of the projectile, in cells per second.
+    pub speed: f64,
+
+    /// The damage dealt by the projectile.
+    pub damage: Damage,
+
+    /// The targeting mode of the projectile.
+    pub targeting: Targeting,
+
+    /// The status effect applied to objects directly hit by the projectile.
+    #[serde(default)]
+    pub effect: Option<Status>,
+
+    /// The number of objects hit so far.
+    pub hits: u32,
+}
+
+/// The targeting mode of a fire object and its projectiles.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum Targeting {
+    /// Hits the first enemy object in the row.
+    #[default]
+    Single,
+    /// Hits the first enemy object in the row,
+    /// and deals reduced damage to the enemy objects
+    /// directly above, below, and beside it.
+    Splash {
+        /// The fraction of the damage dealt to the neighbors,
+        /// between zero and one.
+        falloff: f64,
+    },
+    /// Passes through and hits the first `count` enemy objects in the row.
+    Piercing {
+        /// The maximum number of objects hit, at least one.
+        count: u32,
+    },
+    /// Flies over barriers and other objects,
+    /// and hits the furthest enemy object in the row that is not a barrier.
+    Lobbed,
+}
+
+impl Targeting {
+    /// Checks that the targeting mode can hit at least one object,
+    /// and that the splash falloff is between zero and one.
+    pub fn check(self) -> Result<()> {
+        match self {
+            Targeting::Splash { falloff } if !(0.0..=1.0).contains(&falloff) => {
+                Err(anyhow!("{falloff} is not a valid splash falloff"))
+            }
+            Targeting::Piercing { count: 0 } => {
+                Err(anyhow!("piercing projectiles must hit at least one object"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Projectile {
+    /// Creates a projectile at the center of the specified cell.
+    pub fn new(
+        owner: Player,
+        position: (usize, usize),
+        speed: f64,
+        damage: Damage,
+        targeting: Targeting,
+        effect: Option<Status>,
+    ) -> Self {
+        let (row, column) = position;
+
+        #[allow(clippy::cast_precision_loss)]
+        let column = column as f64 + 0.5;
+
+        Self {
+            owner,
+            row,
+            column,
+            speed,
+            damage,
+            targeting,
+            effect,
+            hits: 0,
+        }
+    }
+
+    /// Returns true if the projectile keeps flying
+    /// after the hits so far.
+    pub fn pierces_further(&self) -> bool {
+        match self.targeting {
+            Targeting::Piercing { count } => self.hits < count,
+            Targeting::Single | Targeting::Splash { .. } | Targeting::Lobbed => false,
+        }
+    }
+
+    /// Returns the direction the projectile travels in:
+    /// `1.0` for rightwards, and `-1.0` for leftwards.
+    pub fn direction(&self) -> f64 {
+        match self.owner {
+            Player::Left => 1.0,
+            Player::Right => -1.0,
+        }
+    }
+
+    /// Moves the projectile by the distance travelled in one tick,
+    /// and returns the column of the cell entered, if any.
+    ///
+    /// A projectile with a valid speed enters at most one cell per tick.
+    /// Columns outside the game area are included;
+    /// they are `-1` or `n_total_columns` when the projectile leaves it.
+    pub fn tick(&mut self) -> Option<i64> {
+        let old_column = self.column;
+        self.column += self.direction() * self.speed / f64::from(TICKS_PER_SECOND);
+
+        #[allow(clippy::cast_possible_truncation)]
+        let (old_cell, new_cell) = (old_column.floor() as i64, self.column.floor() as i64);
+
+        (new_cell != old_cell).then_some(new_cell)
+    }
+}
+
+/// The maximum speed of projectiles, in cells per second,
+/// at which they enter one cell per tick.
+pub const MAX_SPEED: f64 = TICKS_PER_SECOND as f64;
+
+/// Checks that a projectile speed is positive and at most [`MAX_SPEED`],
+/// so that projectiles always travel towards the enemy base
+/// without skipping cells.
+///
+/// [`MAX_SPEED`]: constant.MAX_SPEED.html
+pub fn check_speed(speed: f64) -> Result<()> {
+    if speed > 0.0 && speed <= MAX_SPEED {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "{speed} is not a valid projectile speed (expected at most {MAX_SPEED})"
+        ))
+    }
+}
@@ -1,8 +1,12 @@
 //! Handles game rendering.
 
-use crate::{object, Controller, Object, Player};
+use crate::object::Effect;
+use crate::projectile::Targeting;
+use crate::{object, Controller, Cooldown, Object, Player, Resource, Resources};
 use anyhow::{anyhow, Context as AnyhowContext, Result};
-use graphics::{line, math::Vec2d, rectangle, types::Color, Context, Graphics};
+use graphics::character::CharacterCache;
+use graphics::{line, math::Vec2d, rectangle, text::Text, types::Color, Context, Graphics};
+use piston::{Button, Key};
 
 /// The game renderer.
 pub struct Renderer {
@@ -14,15 +18,25 @@ impl Renderer {
     pub fn new(settings: Settings) -> Result<Self> {
         check_percentage(settings.game_area_percentage).context("invalid game area percentage")?;
         check_percentage(settings.object_percentage).context("invalid object percentage")?;
+        check_percentage(settings.projectile_percentage)
+            .context("invalid projectile percentage")?;
 
         Ok(Self { settings })
     }
 
-    /// Draws the game on the screen.
+    /// Draws the game on the screen,
+    /// using the glyph cache to draw text.
     #[allow(clippy::too_many_lines)]
-    pub fn draw<G>(&self, game_controller: &Controller, context: &Context, g: &mut G) -> Result<()>
+    pub fn draw<C, G>(
+        &self,
+        game_controller: &Controller,
+        glyphs: &mut C,
+        context: &Context,
+        g: &mut G,
+    ) -> Result<()>
     where
-        G: Graphics,
+        C: CharacterCache,
+        G: Graphics<Texture = C::Texture>,
     {
         use graphics::color::TRANSPARENT;
         use std::convert::TryFrom;
@@ -69,30 +83,74 @@ impl Renderer {
         // draw background
         graphics::clear(settings.background_color, g);
 
-        // draw selected cells
-        for player in [Player::Left, Player::Right].iter().copied() {
-            let (row, column) = game_controller.selected_cells()[player];
+        // calculate base layout
+        let base_start: f64 = u32::try_from(game.settings().base_span.start)
+            .context("cannot draw bases")?
+            .into();
+        let base_end: f64 = u32::try_from(game.settings().base_span.end)
+            .context("cannot draw bases")?
+            .into();
 
-            let row: f64 = u32::try_from(row)
-                .context("cannot draw selected cells")?
-                .into();
-            let column: f64 = u32::try_from(column)
-                .context("cannot draw selected cells")?
-                .into();
+        let base_top_y = game_area_top_y + cell_size * base_start;
+        let base_bottom_y = game_area_top_y + cell_size * base_end;
 
-            rectangle::Rectangle::new(settings.selected_cell_color).draw(
-                [
-                    game_area_left_x + column * cell_size,
-                    game_area_top_y + row * cell_size,
-                    cell_size,
-                    cell_size,
-                ],
+        // fill bases according to their health
+        for (player, base_left_x) in [
+            (Player::Left, game_area_left_x),
+            (Player::Right, game_area_right_x - cell_size),
+        ]
+        .iter()
+        .copied()
+        {
+            let base = &game.bases()[player];
+            let health_ratio = f64::from(base.health) / f64::from(base.max_health);
+
+            let filled_area = rectangle::rectangle_by_corners(
+                base_left_x,
+                base_bottom_y - (base_bottom_y - base_top_y) * health_ratio,
+                base_left_x + cell_size,
+                base_bottom_y,
+            );
+            rectangle::Rectangle::new(settings.base_health_color).draw(
+                filled_area,
                 &context.draw_state,
                 context.transform,
                 g,
             );
         }
 
+        // draw cells of objects picked up, then selected cells
+        for player in [Player::Left, Player::Right].iter().copied() {
+            let carried_cell = game_controller
+                .carried_cell(player)
+                .map(|cell| (cell, settings.carried_cell_color));
+            let selected_cell = (
+                game_controller.selected_cells()[player],
+                settings.selected_cell_color,
+            );
+
+            for ((row, column), color) in carried_cell.into_iter().chain(Some(selected_cell)) {
+                let row: f64 = u32::try_from(row)
+                    .context("cannot draw selected cells")?
+                    .into();
+                let column: f64 = u32::try_from(column)
+                    .context("cannot draw selected cells")?
+                    .into();
+
+                rectangle::Rectangle::new(color).draw(
+                    [
+                        game_area_left_x + column * cell_size,
+                        game_area_top_y + row * cell_size,
+                        cell_size,
+                        cell_size,
+                    ],
+                    &context.draw_state,
+                    context.transform,
+                    g,
+                );
+            }
+        }
+
         // draw objects
         for ((row, column), cell) in game.cells().indexed_iter() {
             let row: f64 = u32::try_from(row).context("cannot draw objects")?.into();
@@ -111,6 +169,18 @@ impl Renderer {
             self.draw_object(object, position, cell_size, context, g)?;
         }
 
+        // draw projectiles
+        let projectile_radius = cell_size * settings.projectile_percentage * 0.5;
+        for projectile in game.projectiles() {
+            let row: f64 = u32::try_from(projectile.row)
+                .context("cannot draw projectiles")?
+                .into();
+
+            let x = game_area_left_x + projectile.column * cell_size;
+            let y = game_area_top_y + (row + 0.5) * cell_size;
+            self.draw_projectile(projectile.targeting, [x, y], projectile_radius, context, g);
+        }
+
         // draw vertical cell separators
         for pos in (1..n_columns).chain((n_columns + 1)..n_total_columns) {
             let pos: f64 = u32::try_from(pos)
@@ -155,26 +225,19 @@ impl Renderer {
         // draw base border
         let border = rectangle::Rectangle::new(TRANSPARENT).border(settings.base_border);
 
-        let base_start: f64 = u32::try_from(game.settings().base_span.start)
-            .context("cannot draw base border")?
-            .into();
-        let base_end: f64 = u32::try_from(game.settings().base_span.end)
-            .context("cannot draw base border")?
-            .into();
-
         let left_base = rectangle::rectangle_by_corners(
             game_area_left_x,
-            game_area_top_y + cell_size * base_start,
+            base_top_y,
             game_area_left_x + cell_size,
-            game_area_top_y + cell_size * base_end,
+            base_bottom_y,
         );
         border.draw(left_base, &context.draw_state, context.transform, g);
 
         let right_base = rectangle::rectangle_by_corners(
             game_area_right_x - cell_size,
-            game_area_top_y + cell_size * base_start,
+            base_top_y,
             game_area_right_x,
-            game_area_top_y + cell_size * base_end,
+            base_bottom_y,
         );
         border.draw(right_base, &context.draw_state, context.transform, g);
 
@@ -182,55 +245,449 @@ impl Renderer {
         let border = rectangle::Rectangle::new(TRANSPARENT).border(settings.game_area_border);
         border.draw(game_area, &context.draw_state, context.transform, g);
 
-        // draw the key bar
+        // draw one bar for each resource with a positive cap
+        let caps = game.settings().caps;
+        let resources: Vec<_> = Resource::ALL
+            .iter()
+            .copied()
+            .filter(|&resource| caps[resource] > 0)
+            .collect();
+
         let bottom_margin_height = view_height - game_area_bottom_y;
-        let key_bar_height = bottom_margin_height / 4.0;
+        let n_slots: f64 = u32::try_from(2 * resources.len() + 2)
+            .context("cannot draw resource bars")?
+            .into();
+        let resource_bar_height = bottom_margin_height / n_slots;
+        let resource_bar_width = game_area_width / 2.0;
+
+        for (index, resource) in resources.iter().copied().enumerate() {
+            let index: f64 = u32::try_from(index)
+                .context("cannot draw resource bars")?
+                .into();
+            let resource_bar_top_y = game_area_bottom_y + (2.0 * index + 1.0) * resource_bar_height;
+            let resource_bar_bottom_y = resource_bar_top_y + resource_bar_height;
+
+            let resource_bar_area = rectangle::rectangle_by_corners(
+                game_area_left_x,
+                resource_bar_top_y,
+                game_area_right_x,
+                resource_bar_bottom_y,
+            );
 
-        let key_bar_top_y = game_area_bottom_y + bottom_margin_height / 4.0;
-        let key_bar_bottom_y = game_area_bottom_y + bottom_margin_height / 2.0;
+            let border =
+                rectangle::Rectangle::new(TRANSPARENT).border(settings.resource_bar_border);
+            border.draw(resource_bar_area, &context.draw_state, context.transform, g);
 
-        let key_bar_area = rectangle::rectangle_by_corners(
-            game_area_left_x,
-            key_bar_top_y,
-            game_area_right_x,
-            key_bar_bottom_y,
-        );
+            // draw the resource bar division line
+            settings.resource_bar_division_line.draw_from_to(
+                [center_x, resource_bar_top_y],
+                [center_x, resource_bar_bottom_y],
+                &context.draw_state,
+                context.transform,
+                g,
+            );
+
+            // fill the resource bar
+            let cap: f64 = caps[resource].into();
+
+            for (player, offset) in [(Player::Left, 0.0), (Player::Right, resource_bar_width)]
+                .iter()
+                .copied()
+            {
+                let amount: f64 = game.players()[player].resources[resource].into();
+                let filled_area = [
+                    game_area_left_x + offset,
+                    resource_bar_top_y,
+                    amount / cap * resource_bar_width,
+                    resource_bar_height,
+                ];
+                rectangle::Rectangle::new(settings.resource_bar_colors[resource]).draw(
+                    filled_area,
+                    &context.draw_state,
+                    context.transform,
+                    g,
+                );
+            }
+
+            // write the exact amounts beside the resource bar
+            let padding = resource_bar_height * 0.5;
+            let baseline_y = resource_bar_bottom_y - resource_bar_height * 0.1;
+            for player in [Player::Left, Player::Right].iter().copied() {
+                let text = format!(
+                    "{} {}",
+                    game.players()[player].resources[resource],
+                    resource
+                );
+                let width = text_width(&text, resource_bar_height, glyphs)?;
+                let x = match player {
+                    Player::Left => game_area_left_x - padding - width,
+                    Player::Right => game_area_right_x + padding,
+                };
+                draw_text(
+                    &text,
+                    settings.resource_bar_colors[resource],
+                    resource_bar_height,
+                    [x, baseline_y],
+                    glyphs,
+                    context,
+                    g,
+                )?;
+            }
+        }
+
+        // draw the placement palettes in the side margins
+        let padding = cell_size * 0.1;
+        for (player, left_x) in [
+            (Player::Left, padding),
+            (Player::Right, game_area_right_x + padding),
+        ]
+        .iter()
+        .copied()
+        {
+            let area = [
+                left_x,
+                game_area_top_y,
+                game_area_left_x - 2.0 * padding,
+                game_area_height,
+            ];
+            self.draw_palette(game_controller, player, area, cell_size, glyphs, context, g)?;
+        }
+
+        if game_controller.is_paused() {
+            self.draw_pause_overlay(glyphs, context, g)?;
+        }
+
+        Ok(())
+    }
+
+    /// Draws the placements of a player in the specified area,
+    /// one row for each placement, with rows no taller than `cell_size`.
+    ///
+    /// Each row shows the object to be placed, the key bound to the placement,
+    /// its cost and a bar that shrinks as its cooldown runs out.
+    /// Placements the player cannot afford are greyed out.
+    /// The last row shows why the last action of the player was rejected, if it was.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_palette<C, G>(
+        &self,
+        game_controller: &Controller,
+        player: Player,
+        area: graphics::types::Rectangle,
+        cell_size: f64,
+        glyphs: &mut C,
+        context: &Context,
+        g: &mut G,
+    ) -> Result<()>
+    where
+        C: CharacterCache,
+        G: Graphics<Texture = C::Texture>,
+    {
+        use std::convert::TryFrom;
+
+        let settings = &self.settings;
+        let data = &game_controller.game().players()[player];
+        let key_binding = &game_controller.settings().key_binding[player];
+
+        let [left_x, top_y, width, height] = area;
+        // one more row for the action error
+        let n_rows: f64 = u32::try_from(data.placements.len() + 1)
+            .context("cannot draw palette")?
+            .into();
+        let row_height = (height / n_rows).min(cell_size);
+        let font_size = row_height * 0.3;
+
+        let text_x = left_x + row_height * 1.1;
+        let bar_width = (left_x + width - text_x).max(0.0);
+
+        for (index, placement) in data.placements.iter().enumerate() {
+            let row: f64 = u32::try_from(index).context("cannot draw palette")?.into();
+            let row_top_y = top_y + row * row_height;
+
+            self.draw_object(
+                &placement.object,
+                [left_x, row_top_y],
+                row_height,
+                context,
+                g,
+            )?;
+
+            // write the bound key
+            let label = key_binding
+                .place
+                .get(index)
+                .map_or_else(|| "-".to_owned(), |&button| button_label(button));
+            draw_text(
+                &label,
+                settings.text_color,
+                font_size,
+                [text_x, row_top_y + row_height * 0.4],
+                glyphs,
+                context,
+                g,
+            )?;
+
+            // write the cost, each resource in the color of its bar
+            let mut cost_x = text_x;
+            for resource in Resource::ALL.iter().copied() {
+                let amount = placement.cost[resource];
+                if amount == 0 {
+                    continue;
+                }
+                let text = format!("{amount} ");
+                draw_text(
+                    &text,
+                    settings.resource_bar_colors[resource],
+                    font_size,
+                    [cost_x, row_top_y + row_height * 0.75],
+                    glyphs,
+                    context,
+                    g,
+                )?;
+                cost_x += text_width(&text, font_size, glyphs)?;
+            }
+
+            // draw the remaining cooldown
+            let cooldown = &placement.cooldown;
+            if !cooldown.is_over() {
+                let remaining = 1.0 - cooldown.progress();
+                rectangle::Rectangle::new(settings.palette_cooldown_color).draw(
+                    [
+                        text_x,
+                        row_top_y + row_height * 0.85,
+                        bar_width * remaining,
+                        row_height * 0.08,
+                    ],
+                    &context.draw_state,
+                    context.transform,
+                    g,
+                );
+            }
+
+            if data.resources.shortage(&placement.cost).is_some() {
+                rectangle::Rectangle::new(settings.unaffordable_color).draw(
+                    [left_x, row_top_y, width, row_height],
+                    &context.draw_state,
+                    context.transform,
+                    g,
+                );
+            }
+        }
 
-        let border = rectangle::Rectangle::new(TRANSPARENT).border(settings.key_bar_border);
-        border.draw(key_bar_area, &context.draw_state, context.transform, g);
+        if let Some(error) = game_controller.action_error(player) {
+            let row: f64 = u32::try_from(data.placements.len())
+                .context("cannot draw palette")?
+                .into();
+            let text = error.to_string();
+
+            // shrink the text if it does not fit in the palette
+            let full_width = text_width(&text, font_size, glyphs)?;
+            let font_size = if full_width > width {
+                font_size * width / full_width
+            } else {
+                font_size
+            };
+
+            draw_text(
+                &text,
+                settings.action_error_color,
+                font_size,
+                [left_x, top_y + (row + 0.5) * row_height],
+                glyphs,
+                context,
+                g,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Dims the screen and shows that the game is paused.
+    pub fn draw_pause_overlay<C, G>(
+        &self,
+        glyphs: &mut C,
+        context: &Context,
+        g: &mut G,
+    ) -> Result<()>
+    where
+        C: CharacterCache,
+        G: Graphics<Texture = C::Texture>,
+    {
+        use graphics::Transformed;
+
+        const TEXT: &str = "PAUSED";
 
-        // draw the key bar division line
-        settings.key_bar_division_line.draw_from_to(
-            [center_x, key_bar_top_y],
-            [center_x, key_bar_bottom_y],
+        let settings = &self.settings;
+        let [view_width, view_height] = context.get_view_size();
+
+        rectangle::Rectangle::new(settings.pause_overlay_color).draw(
+            [0.0, 0.0, view_width, view_height],
             &context.draw_state,
             context.transform,
             g,
         );
 
-        // fill the key bar
-        let key_bar_width = game_area_width / 2.0;
-        let max_keys: f64 = game.settings().max_keys.into();
+        let text = settings.pause_text;
+        let width = glyphs
+            .width(text.font_size, TEXT)
+            .map_err(|_| anyhow!("cannot measure pause text"))?;
+        let transform = context.transform.trans(
+            (view_width - width) * 0.5,
+            (view_height + f64::from(text.font_size)) * 0.5,
+        );
+        text.draw(TEXT, glyphs, &context.draw_state, transform, g)
+            .map_err(|_| anyhow!("cannot draw pause text"))
+    }
 
-        for (player, offset) in [(Player::Left, 0.0), (Player::Right, key_bar_width)]
-            .iter()
-            .copied()
-        {
-            let filled_area = [
-                game_area_left_x + offset,
-                key_bar_top_y,
-                f64::from(game.players()[player].keys) / max_keys * key_bar_width,
-                key_bar_height,
-            ];
-            rectangle::Rectangle::new(settings.key_bar_color).draw(
-                filled_area,
+    /// Draws the upgrade level of an object as a row of pips,
+    /// starting at the specified position.
+    pub fn draw_level<G>(&self, level: u32, start: Vec2d, radius: f64, context: &Context, g: &mut G)
+    where
+        G: Graphics,
+    {
+        use graphics::ellipse;
+
+        let pip = ellipse::Ellipse::new(self.settings.level_color);
+        for index in 0..level {
+            let center_x = start[0] + radius * 3.0 * f64::from(index);
+            pip.draw(
+                ellipse::circle(center_x, start[1], radius),
                 &context.draw_state,
                 context.transform,
                 g,
             );
         }
+    }
 
-        Ok(())
+    /// Draws the icon of a status effect centered at the specified position.
+    ///
+    /// Each effect has its own shape.
+    pub fn draw_status_icon<G>(
+        &self,
+        effect: Effect,
+        center: Vec2d,
+        radius: f64,
+        context: &Context,
+        g: &mut G,
+    ) where
+        G: Graphics,
+    {
+        use graphics::{ellipse, polygon};
+
+        let [x, y] = center;
+        let color = self.settings.status_color;
+
+        match effect {
+            Effect::Burn { .. } => {
+                // draw flame
+                polygon::Polygon::new(color).draw(
+                    &[
+                        [x - radius, y + radius],
+                        [x + radius, y + radius],
+                        [x, y - radius],
+                    ],
+                    &context.draw_state,
+                    context.transform,
+                    g,
+                );
+            }
+            Effect::Slow { .. } => {
+                // draw hollow ring
+                ellipse::Ellipse::new_border(color, radius * 0.3).draw(
+                    ellipse::circle(x, y, radius),
+                    &context.draw_state,
+                    context.transform,
+                    g,
+                );
+            }
+            Effect::Shield { .. } => {
+                // draw hollow square
+                rectangle::Rectangle::new_border(color, radius * 0.3).draw(
+                    rectangle::centered_square(x, y, radius),
+                    &context.draw_state,
+                    context.transform,
+                    g,
+                );
+            }
+            Effect::Stun => {
+                // draw cross
+                let line = line::Line::new(color, radius * 0.3);
+                for (from, to) in [
+                    ([x - radius, y - radius], [x + radius, y + radius]),
+                    ([x - radius, y + radius], [x + radius, y - radius]),
+                ]
+                .iter()
+                .copied()
+                {
+                    line.draw_from_to(from, to, &context.draw_state, context.transform, g);
+                }
+            }
+        }
+    }
+
+    /// Draws a projectile centered at the specified position.
+    ///
+    /// Each targeting mode has its own shape.
+    pub fn draw_projectile<G>(
+        &self,
+        targeting: Targeting,
+        center: Vec2d,
+        radius: f64,
+        context: &Context,
+        g: &mut G,
+    ) where
+        G: Graphics,
+    {
+        use graphics::ellipse;
+
+        let settings = &self.settings;
+        let [x, y] = center;
+        let color = settings.projectile_color;
+
+        match targeting {
+            Targeting::Single => {
+                // draw dot
+                ellipse::Ellipse::new(color).draw(
+                    ellipse::circle(x, y, radius),
+                    &context.draw_state,
+                    context.transform,
+                    g,
+                );
+            }
+            Targeting::Splash { .. } => {
+                // draw dot surrounded by a ring
+                ellipse::Ellipse::new(color).draw(
+                    ellipse::circle(x, y, radius),
+                    &context.draw_state,
+                    context.transform,
+                    g,
+                );
+                ellipse::Ellipse::new_border(color, radius * 0.25).draw(
+                    ellipse::circle(x, y, radius * 2.0),
+                    &context.draw_state,
+                    context.transform,
+                    g,
+                );
+            }
+            Targeting::Piercing { .. } => {
+                // draw horizontal bolt
+                line::Line::new(color, radius * 0.5).draw_from_to(
+                    [x - radius * 2.5, y],
+                    [x + radius * 2.5, y],
+                    &context.draw_state,
+                    context.transform,
+                    g,
+                );
+            }
+            Targeting::Lobbed => {
+                // draw hollow ring
+                ellipse::Ellipse::new_border(color, radius * 0.4).draw(
+                    ellipse::circle(x, y, radius * 1.5),
+                    &context.draw_state,
+                    context.transform,
+                    g,
+                );
+            }
+        }
     }
 
     /// Draws the object at the specified position.
@@ -300,18 +757,134 @@ impl Renderer {
                 );
                 rectangle.draw(object_area, &context.draw_state, context.transform, g);
             }
+            Kind::Healer { .. } => {
+                // draw cross
+                let arm = object_size / 6.0;
+                let outline = [
+                    [center_x - arm, object_top_y],
+                    [center_x + arm, object_top_y],
+                    [center_x + arm, center_y - arm],
+                    [object_right_x, center_y - arm],
+                    [object_right_x, center_y + arm],
+                    [center_x + arm, center_y + arm],
+                    [center_x + arm, object_bottom_y],
+                    [center_x - arm, object_bottom_y],
+                    [center_x - arm, center_y + arm],
+                    [object_left_x, center_y + arm],
+                    [object_left_x, center_y - arm],
+                    [center_x - arm, center_y - arm],
+                ];
+
+                let line = line::Line::new(
+                    settings.object_outline_color,
+                    settings.object_outline_radius,
+                );
+                draw_polygon_border(line, &outline, context, g);
+            }
+            Kind::Caster { .. } => {
+                // draw diamond
+                let outline = [
+                    [center_x, object_top_y],
+                    [object_right_x, center_y],
+                    [center_x, object_bottom_y],
+                    [object_left_x, center_y],
+                ];
+
+                let line = line::Line::new(
+                    settings.object_outline_color,
+                    settings.object_outline_radius,
+                );
+                draw_polygon_border(line, &outline, context, g);
+            }
+        }
+
+        // draw a ring around the object that closes as its cooldown runs out
+        if let Kind::Key { cooldown, .. } | Kind::Fire { cooldown, .. } = &object.kind {
+            self.draw_cooldown_ring(cooldown, [center_x, center_y], object_size, context, g);
+        }
+
+        // draw upgrade level above the object
+        let pip_radius = (object_top_y - cell_top_y) * 0.25;
+        let pips_start = [object_left_x + pip_radius, cell_top_y + pip_radius * 2.0];
+        self.draw_level(object.level, pips_start, pip_radius, context, g);
+
+        // draw status effects below the object
+        let icons_start = [
+            object_left_x + pip_radius,
+            object_bottom_y + (cell_top_y + cell_size - object_bottom_y) * 0.5,
+        ];
+        for (index, status) in object.statuses.iter().enumerate() {
+            #[allow(clippy::cast_precision_loss)]
+            let offset = pip_radius * 3.0 * index as f64;
+            let center = [icons_start[0] + offset, icons_start[1]];
+            self.draw_status_icon(status.effect, center, pip_radius, context, g);
         }
 
         // draw health bar
+        let health_bar_center = [
+            object_right_x + (cell_right_x - object_right_x) * 0.5,
+            center_y,
+        ];
+        self.draw_health_bar(object, health_bar_center, cell_size, context, g);
+
+        Ok(())
+    }
+
+    /// Draws a ring around an object of the specified size
+    /// that closes clockwise as the cooldown runs out.
+    /// Nothing is drawn once the cooldown is over.
+    pub fn draw_cooldown_ring<G>(
+        &self,
+        cooldown: &Cooldown,
+        center: Vec2d,
+        object_size: f64,
+        context: &Context,
+        g: &mut G,
+    ) where
+        G: Graphics,
+    {
+        use std::f64::consts::{FRAC_PI_2, TAU};
+
+        let settings = &self.settings;
+
+        let progress = cooldown.progress();
+        if progress >= 1.0 {
+            return;
+        }
+
+        let ring_area = rectangle::centered_square(
+            center[0],
+            center[1],
+            object_size * 0.5 * settings.cooldown_ring_percentage,
+        );
+        graphics::CircleArc::new(
+            settings.cooldown_ring_color,
+            settings.cooldown_ring_radius,
+            -FRAC_PI_2,
+            -FRAC_PI_2 + TAU * progress,
+        )
+        .draw(ring_area, &context.draw_state, context.transform, g);
+    }
+
+    /// Draws the health bar of an object centered at the specified position.
+    pub fn draw_health_bar<G>(
+        &self,
+        object: &Object,
+        center: Vec2d,
+        cell_size: f64,
+        context: &Context,
+        g: &mut G,
+    ) where
+        G: Graphics,
+    {
+        let settings = &self.settings;
+
         let health_bar_height = cell_size * settings.health_bar_height_percentage;
         let health_bar_width = cell_size * settings.health_bar_width_percentage;
 
-        let health_bar_center_x = (object_right_x + cell_right_x) / 2.0;
-        let health_bar_center_y = center_y;
-
         let health_bar_area = rectangle::centered([
-            health_bar_center_x,
-            health_bar_center_y,
+            center[0],
+            center[1],
             health_bar_width / 2.0,
             health_bar_height / 2.0,
         ]);
@@ -341,8 +914,6 @@ impl Renderer {
             context.transform,
             g,
         );
-
-        Ok(())
     }
 }
 
@@ -377,6 +948,10 @@ pub struct Settings {
     /// The border of the bases.
     pub base_border: rectangle::Border,
 
+    /// The color to fill the bases,
+    /// in proportion to their remaining health.
+    pub base_health_color: Color,
+
     /// The percentage of a cell taken up by the object within,
     /// in both the horizontal and vertical directions.
     pub object_percentage: f64,
@@ -387,17 +962,45 @@ pub struct Settings {
     /// The radius of outlines of objects
     pub object_outline_radius: f64,
 
+    /// The diameter of the rings that show the cooldown progress of objects,
+    /// as a percentage of the object size.
+    ///
+    /// Values above `1.0` put the rings outside the objects.
+    pub cooldown_ring_percentage: f64,
+
+    /// The color of the rings that show the cooldown progress of objects.
+    pub cooldown_ring_color: Color,
+
+    /// The radius of the rings that show the cooldown progress of objects.
+    pub cooldown_ring_radius: f64,
+
+    /// The color of the pips that show the upgrade level of objects.
+    pub level_color: Color,
+
+    /// The color of the icons that show the status effects on objects.
+    pub status_color: Color,
+
+    /// The diameter of projectiles,
+    /// as a percentage of the cell size.
+    pub projectile_percentage: f64,
+
+    /// The color of projectiles.
+    pub projectile_color: Color,
+
     /// The color of cells selected by players.
     pub selected_cell_color: Color,
 
-    /// The border of the key bar.
-    pub key_bar_border: rectangle::Border,
+    /// The color of cells whose objects are picked up by players.
+    pub carried_cell_color: Color,
 
-    /// The line that separates the players' areas in the key bar.
-    pub key_bar_division_line: line::Line,
+    /// The border of the resource bars.
+    pub resource_bar_border: rectangle::Border,
 
-    /// The color to fill the key bar.
-    pub key_bar_color: Color,
+    /// The line that separates the players' areas in the resource bars.
+    pub resource_bar_division_line: line::Line,
+
+    /// The color to fill the bar of each resource.
+    pub resource_bar_colors: Resources<Color>,
 
     /// The height of the health bar,
     /// as a percentage of the cell size.
@@ -412,17 +1015,115 @@ pub struct Settings {
 
     /// The color to fill the health bar.
     pub health_bar_color: Color,
+
+    /// The color of text in the placement palettes.
+    pub text_color: Color,
+
+    /// The color of the bars that show the remaining cooldown of placements.
+    pub palette_cooldown_color: Color,
+
+    /// The color laid over placements that the player cannot afford.
+    pub unaffordable_color: Color,
+
+    /// The color of the reason why the last action was rejected.
+    pub action_error_color: Color,
+
+    /// The color laid over the screen while the game is paused.
+    pub pause_overlay_color: Color,
+
+    /// The text that shows that the game is paused.
+    pub pause_text: Text,
 }
 
 /// Checks that the argument is within the range [0.0, 1.0].
 fn check_percentage(number: f64) -> Result<()> {
-    if number >= 0.0 && number <= 1.0 {
+    if (0.0..=1.0).contains(&number) {
         Ok(())
     } else {
-        Err(anyhow!("{} is not a valid percentage", number))
+        Err(anyhow!("{number} is not a valid percentage"))
     }
 }
 
+/// Draws a line of text starting at the specified point on the baseline.
+fn draw_text<C, G>(
+    text: &str,
+    color: Color,
+    font_size: f64,
+    start: Vec2d,
+    glyphs: &mut C,
+    context: &Context,
+    g: &mut G,
+) -> Result<()>
+where
+    C: CharacterCache,
+    G: Graphics<Texture = C::Texture>,
+{
+    use graphics::Transformed;
+
+    Text::new_color(color, pixels(font_size))
+        .draw(
+            text,
+            glyphs,
+            &context.draw_state,
+            context.transform.trans(start[0], start[1]),
+            g,
+        )
+        .map_err(|_| anyhow!("cannot draw text {text:?}"))
+}
+
+/// Returns the width of a line of text.
+fn text_width<C>(text: &str, font_size: f64, glyphs: &mut C) -> Result<f64>
+where
+    C: CharacterCache,
+{
+    glyphs
+        .width(pixels(font_size), text)
+        .map_err(|_| anyhow!("cannot measure text {text:?}"))
+}
+
+/// Converts a font size to whole pixels, at least one.
+fn pixels(font_size: f64) -> u32 {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let pixels = font_size.round().max(1.0) as u32;
+    pixels
+}
+
+/// Returns a short label for a button.
+pub(crate) fn button_label(button: Button) -> String {
+    let Button::Keyboard(key) = button else {
+        return format!("{button:?}");
+    };
+
+    let label = match key {
+        Key::LeftBracket => "[",
+        Key::RightBracket => "]",
+        Key::Backslash => "\\",
+        Key::Semicolon => ";",
+        Key::Quote => "'",
+        Key::Comma => ",",
+        Key::Period => ".",
+        Key::Slash => "/",
+        Key::Minus => "-",
+        Key::Equals => "=",
+        Key::NumPadDivide => "Num /",
+        Key::NumPadMultiply => "Num *",
+        Key::NumPadMinus => "Num -",
+        Key::NumPadPlus => "Num +",
+        Key::NumPadPeriod => "Num .",
+        _ => {
+            let name = format!("{key:?}");
+            if let Some(rest) = name.strip_prefix("NumPad") {
+                return format!("Num {rest}");
+            }
+            return match name.strip_prefix('D') {
+                Some(digit) if digit.len() == 1 => digit.to_owned(),
+                _ => name,
+            };
+        }
+    };
+    label.to_owned()
+}
+
 /// Draws the specified polygon border.
 fn draw_polygon_border<G>(
     line: line::Line,
@@ -0,0 +1,110 @@
+//! Command logs and deterministic replays.
+//!
+//! A replay consists of the initial game state
+//! and every [`Command`] issued by the players,
+//! stamped with the simulation tick at which it was issued.
+//! Feeding the commands back at the same ticks
+//! reproduces the match frame for frame.
+//!
+//! [`Command`]: ../controller/enum.Command.html
+
+use crate::controller::Command;
+use crate::{Player, Players, Snapshot};
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// The current replay format version.
+pub const VERSION: u32 = 1;
+
+/// A recorded match.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Replay {
+    /// The replay format version.
+    pub version: u32,
+
+    /// The game state when the recording started.
+    pub initial: Snapshot,
+
+    /// The selected cells when the recording started.
+    pub selected_cells: Players<(usize, usize)>,
+
+    /// The commands, in the order they were issued.
+    pub records: Vec<Record>,
+}
+
+/// A command issued by a player.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Record {
+    /// The game tick at which the command was issued,
+    /// counted from the start of the game rather than the recording.
+    pub tick: u64,
+
+    /// The player who issued the command.
+    pub player: Player,
+
+    /// The command.
+    pub command: Command,
+}
+
+/// The fields shared by all replay format versions.
+#[derive(Deserialize)]
+struct Header {
+    version: u32,
+}
+
+impl Replay {
+    /// Serializes the replay into a RON string.
+    pub fn to_ron(&self) -> Result<String> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .context("cannot serialize replay")
+    }
+
+    /// Parses a replay from a RON string.
+    ///
+    /// The format version is checked before the rest of the replay is parsed,
+    /// so that replays of other versions are reported as such.
+    pub fn from_ron(source: &str) -> Result<Self> {
+        let header: Header = ron::from_str(source).context("cannot parse replay")?;
+        if header.version != VERSION {
+            return Err(anyhow!(
+                "unsupported replay version {} (expected {})",
+                header.version,
+                VERSION
+            ));
+        }
+
+        ron::from_str(source).context("cannot parse replay")
+    }
+
+    /// Saves the replay to the specified path.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_ron()?)
+            .with_context(|| format!("cannot write replay {}", path.display()))
+    }
+
+    /// Loads a replay from the specified path.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("cannot read replay {}", path.display()))?;
+        Self::from_ron(&source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn other_versions_are_rejected_before_parsing() {
+        let source = "(version: 0, records: [])";
+        let error = Replay::from_ron(source).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unsupported replay version 0 (expected 1)"
+        );
+    }
+}
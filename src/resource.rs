@@ -0,0 +1,168 @@
+//! The resources spent by players.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A kind of resource.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+pub enum Resource {
+    /// Keys, the main resource.
+    #[default]
+    Keys,
+    /// Energy, spent on advanced placements.
+    Energy,
+}
+
+impl Resource {
+    /// All kinds of resources.
+    pub const ALL: [Resource; 2] = [Resource::Keys, Resource::Energy];
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Resource::Keys => write!(f, "keys"),
+            Resource::Energy => write!(f, "energy"),
+        }
+    }
+}
+
+/// A container that holds the same data for each kind of resource.
+///
+/// Amounts of resources, such as costs and caps,
+/// are stored in a `Resources<u32>`.
+/// Resources that are left out in RON files default to zero.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Resources<T> {
+    /// The data associated with keys.
+    pub keys: T,
+    /// The data associated with energy.
+    pub energy: T,
+}
+
+impl<T> std::ops::Index<Resource> for Resources<T> {
+    type Output = T;
+
+    fn index(&self, index: Resource) -> &T {
+        match index {
+            Resource::Keys => &self.keys,
+            Resource::Energy => &self.energy,
+        }
+    }
+}
+
+impl<T> std::ops::IndexMut<Resource> for Resources<T> {
+    fn index_mut(&mut self, index: Resource) -> &mut T {
+        match index {
+            Resource::Keys => &mut self.keys,
+            Resource::Energy => &mut self.energy,
+        }
+    }
+}
+
+impl Resources<u32> {
+    /// Returns the first resource of which there is less than `cost`,
+    /// or `None` if the cost can be paid.
+    pub fn shortage(&self, cost: &Self) -> Option<Resource> {
+        Resource::ALL
+            .iter()
+            .copied()
+            .find(|&resource| self[resource] < cost[resource])
+    }
+
+    /// Subtracts the cost, stopping at zero.
+    pub fn spend(&mut self, cost: &Self) {
+        for resource in Resource::ALL.iter().copied() {
+            self[resource] = self[resource].saturating_sub(cost[resource]);
+        }
+    }
+
+    /// Adds the amount, up to the caps,
+    /// and returns the amount actually added.
+    #[must_use]
+    pub fn gain(&mut self, amount: &Self, caps: &Self) -> Self {
+        let mut gained = Self::default();
+        for resource in Resource::ALL.iter().copied() {
+            let old = self[resource];
+            self[resource] = old
+                .saturating_add(amount[resource])
+                .min(caps[resource])
+                .max(old);
+            gained[resource] = self[resource] - old;
+        }
+        gained
+    }
+
+    /// Returns the amounts multiplied by the factor, rounded down.
+    #[must_use]
+    pub fn scale(&self, factor: f64) -> Self {
+        let mut scaled = Self::default();
+        for resource in Resource::ALL.iter().copied() {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let amount = (f64::from(self[resource]) * factor).floor() as u32;
+            scaled[resource] = amount;
+        }
+        scaled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn costs_are_paid_from_each_resource() {
+        let mut resources = Resources {
+            keys: 50,
+            energy: 10,
+        };
+        let cost = Resources {
+            keys: 20,
+            energy: 15,
+        };
+
+        assert_eq!(resources.shortage(&cost), Some(Resource::Energy));
+        resources.energy = 15;
+        assert_eq!(resources.shortage(&cost), None);
+
+        resources.spend(&cost);
+        assert_eq!(
+            resources,
+            Resources {
+                keys: 30,
+                energy: 0
+            }
+        );
+    }
+
+    #[test]
+    fn gains_are_capped() {
+        let mut resources = Resources {
+            keys: 90,
+            energy: 0,
+        };
+        let caps = Resources {
+            keys: 100,
+            energy: 100,
+        };
+
+        let gained = resources.gain(
+            &Resources {
+                keys: 20,
+                energy: 20,
+            },
+            &caps,
+        );
+        assert_eq!(
+            gained,
+            Resources {
+                keys: 10,
+                energy: 20
+            }
+        );
+        assert_eq!(resources.keys, 100);
+    }
+}
@@ -0,0 +1,204 @@
+//! Saving and loading match state.
+//!
+//! A snapshot captures everything needed to restore a game exactly,
+//! including the remaining cooldown ticks of every object and placement.
+//! Snapshots are stored in the [RON] format
+//! and carry a format version,
+//! so that incompatible files are rejected instead of misread.
+//!
+//! [RON]: https://github.com/ron-rs/ron
+
+use crate::{game, object, player, Game, Players, Projectile};
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// The current snapshot format version.
+pub const VERSION: u32 = 1;
+
+/// A snapshot of the game state.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Snapshot {
+    /// The snapshot format version.
+    pub version: u32,
+
+    /// The game settings.
+    pub settings: game::Settings,
+
+    /// The number of ticks simulated.
+    pub tick: u64,
+
+    /// The player data.
+    pub players: Players<player::Data>,
+
+    /// The bases.
+    pub bases: Players<game::Base>,
+
+    /// The objects on the board and their positions.
+    pub objects: Vec<((usize, usize), object::Owned)>,
+
+    /// The projectiles in flight.
+    pub projectiles: Vec<Projectile>,
+}
+
+/// The fields shared by all snapshot format versions.
+#[derive(Deserialize)]
+struct Header {
+    version: u32,
+}
+
+impl Snapshot {
+    /// Takes a snapshot of the specified game.
+    pub fn of(game: &Game) -> Self {
+        let objects = game
+            .cells()
+            .indexed_iter()
+            .filter_map(|(position, cell)| Some((position, cell.borrow().object.clone()?)))
+            .collect();
+
+        Self {
+            version: VERSION,
+            settings: game.settings().clone(),
+            tick: game.tick(),
+            players: game.players().clone(),
+            bases: game.bases().clone(),
+            objects,
+            projectiles: game.projectiles().to_vec(),
+        }
+    }
+
+    /// Restores the game from the snapshot.
+    ///
+    /// The objects are checked like the templates of a catalog,
+    /// so that a modified snapshot cannot inject invalid objects.
+    pub fn restore(self) -> Result<Game> {
+        let mut builder = game::Builder::new(self.settings)?;
+        for (position, object) in self.objects {
+            builder = builder.object(position, object)?;
+        }
+
+        builder
+            .players(self.players)
+            .bases(self.bases)
+            .projectiles(self.projectiles)
+            .tick(self.tick)
+            .finish()
+    }
+
+    /// Serializes the snapshot into a RON string.
+    pub fn to_ron(&self) -> Result<String> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .context("cannot serialize snapshot")
+    }
+
+    /// Parses a snapshot from a RON string.
+    ///
+    /// The format version is checked before the rest of the snapshot is parsed,
+    /// so that snapshots of other versions are reported as such.
+    pub fn from_ron(source: &str) -> Result<Self> {
+        let header: Header = ron::from_str(source).context("cannot parse snapshot")?;
+        if header.version != VERSION {
+            return Err(anyhow!(
+                "unsupported snapshot version {} (expected {})",
+                header.version,
+                VERSION
+            ));
+        }
+
+        ron::from_str(source).context("cannot parse snapshot")
+    }
+
+    /// Saves the snapshot to the specified path.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_ron()?)
+            .with_context(|| format!("cannot write snapshot {}", path.display()))
+    }
+
+    /// Loads a snapshot from the specified path.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("cannot read snapshot {}", path.display()))?;
+        Self::from_ron(&source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Catalog, Player};
+
+    /// Updates the game by the specified number of ticks.
+    fn run(game: &mut Game, ticks: u32) {
+        for _ in 0..ticks {
+            game.update();
+        }
+    }
+
+    #[test]
+    fn round_trip_mid_match() {
+        let mut game = Catalog::builtin().unwrap().game().unwrap();
+        run(&mut game, 90);
+        game.place_object(Player::Left, (0, 0), 0).unwrap();
+        game.place_object(Player::Right, (1, 11), 1).unwrap();
+        run(&mut game, 150);
+
+        let source = Snapshot::of(&game).to_ron().unwrap();
+        let mut restored = Snapshot::from_ron(&source).unwrap().restore().unwrap();
+        assert_eq!(Snapshot::of(&restored).to_ron().unwrap(), source);
+
+        // the restored game continues exactly like the original game
+        run(&mut game, 600);
+        run(&mut restored, 600);
+        assert_eq!(
+            Snapshot::of(&restored).to_ron().unwrap(),
+            Snapshot::of(&game).to_ron().unwrap()
+        );
+    }
+
+    #[test]
+    fn objects_are_checked_on_restore() {
+        let snapshot = Snapshot::of(&Catalog::builtin().unwrap().game().unwrap());
+        let fire = snapshot
+            .players
+            .left
+            .placements
+            .iter()
+            .position(|placement| matches!(placement.object.kind, object::Kind::Fire { .. }))
+            .unwrap();
+        let with_speed = |mut object: object::Object, new_speed: f64| {
+            if let object::Kind::Fire { speed, .. } = &mut object.kind {
+                *speed = new_speed;
+            }
+            object
+        };
+
+        let mut invalid = snapshot.clone();
+        let object = invalid.players.left.placements[fire].object.clone();
+        invalid.objects.push((
+            (0, 0),
+            object::Owned {
+                object: with_speed(object, f64::NAN),
+                owner: Player::Left,
+            },
+        ));
+        assert!(invalid.restore().is_err());
+
+        let mut invalid = snapshot;
+        let placement = &mut invalid.players.right.placements[fire];
+        placement.object = with_speed(placement.object.clone(), -1.0);
+        assert!(invalid.restore().is_err());
+    }
+
+    #[test]
+    fn other_versions_are_rejected_before_parsing() {
+        let source = "(version: 0, board: [])";
+        let error = Snapshot::from_ron(source).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unsupported snapshot version 0 (expected 1)"
+        );
+    }
+}
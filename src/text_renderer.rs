@@ -0,0 +1,299 @@
+//! Renders the game as plain text, for terminals.
+//!
+//! Each cell of the board is drawn as five characters:
+//! the selection mark, the object symbol, the health digit,
+//! the projectile mark and the closing selection mark.
+//! Objects of the left player are drawn in upper case
+//! and objects of the right player in lower case.
+//!
+//! | Symbol | Object  |
+//! |--------|---------|
+//! | `K`    | key     |
+//! | `F`    | fire    |
+//! | `#`    | barrier |
+//! | `+`    | healer  |
+//! | `*`    | caster  |
+//!
+//! The health digit runs from `9` at full health down to `0`.
+//! Selected cells are enclosed in `[` and `]`,
+//! and cells whose objects are picked up in `<` and `>`.
+
+use crate::game::Outcome;
+use crate::object::Kind;
+use crate::renderer::button_label;
+use crate::tick::TICKS_PER_SECOND;
+use crate::{Controller, Object, Player, Resource};
+use std::fmt::Write;
+
+/// The text renderer.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TextRenderer;
+
+impl TextRenderer {
+    /// Creates a new text renderer.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Renders the game as lines of text.
+    pub fn render(&self, game_controller: &Controller) -> Vec<String> {
+        let mut lines = vec![render_status(game_controller)];
+        for player in [Player::Left, Player::Right].iter().copied() {
+            lines.push(render_player(game_controller, player));
+        }
+        lines.push(String::new());
+        lines.extend(render_board(game_controller));
+        lines.push(String::new());
+        for player in [Player::Left, Player::Right].iter().copied() {
+            lines.push(render_palette(game_controller, player));
+        }
+        lines
+    }
+}
+
+/// Renders the outcome, the game time, the speed and whether the game is paused.
+fn render_status(game_controller: &Controller) -> String {
+    let game = game_controller.game();
+    let seconds = game.tick() / u64::from(TICKS_PER_SECOND);
+
+    let mut line = format!(
+        "{:02}:{:02}  {}x",
+        seconds / 60,
+        seconds % 60,
+        game_controller.speed().factor()
+    );
+    match game.outcome() {
+        Outcome::Ongoing => {}
+        Outcome::Won(Player::Left) => line.push_str("  left player wins"),
+        Outcome::Won(Player::Right) => line.push_str("  right player wins"),
+        Outcome::Draw => line.push_str("  draw"),
+    }
+    if game_controller.is_paused() {
+        line.push_str("  PAUSED");
+    }
+    line
+}
+
+/// Renders the base health and resources of a player.
+fn render_player(game_controller: &Controller, player: Player) -> String {
+    let game = game_controller.game();
+    let base = &game.bases()[player];
+    let data = &game.players()[player];
+
+    let mut line = format!(
+        "{:<5} base {}/{}",
+        match player {
+            Player::Left => "left",
+            Player::Right => "right",
+        },
+        base.health,
+        base.max_health
+    );
+    for resource in Resource::ALL.iter().copied() {
+        let cap = game.settings().caps[resource];
+        if cap > 0 {
+            let _ = write!(line, "  {} {}/{}", resource, data.resources[resource], cap);
+        }
+    }
+    line
+}
+
+/// Renders the board, one line for each row.
+fn render_board(game_controller: &Controller) -> Vec<String> {
+    let game = game_controller.game();
+    let settings = game.settings();
+    let n_columns = settings.n_columns;
+    let n_total_columns = 2 * n_columns;
+
+    let border = format!("+{}+", "-".repeat(n_total_columns * 5 + 1));
+
+    let cells = game.cells();
+    let mut lines = vec![border.clone()];
+    for row in 0..settings.n_rows {
+        let in_base = settings.base_span.contains(&row);
+        let mut line = String::from(if in_base { "B" } else { "|" });
+
+        for column in 0..n_total_columns {
+            if column == n_columns {
+                line.push('|');
+            }
+
+            let position = (row, column);
+            let (open, close) = if game_controller.selected_cells().left == position
+                || game_controller.selected_cells().right == position
+            {
+                ('[', ']')
+            } else if game_controller.carried_cell(Player::Left) == Some(position)
+                || game_controller.carried_cell(Player::Right) == Some(position)
+            {
+                ('<', '>')
+            } else {
+                (' ', ' ')
+            };
+
+            let cell = cells[position].borrow();
+            let (symbol, health) = match &cell.object {
+                Some(owned) => (
+                    object_symbol(&owned.object, owned.owner),
+                    health_digit(&owned.object),
+                ),
+                None => ('.', ' '),
+            };
+
+            let projectile = game
+                .projectiles()
+                .iter()
+                .find(|projectile| {
+                    projectile.row == row && column_of(projectile.column) == Some(column)
+                })
+                .map_or(' ', |projectile| match projectile.owner {
+                    Player::Left => '>',
+                    Player::Right => '<',
+                });
+
+            line.push(open);
+            line.push(symbol);
+            line.push(health);
+            line.push(projectile);
+            line.push(close);
+        }
+
+        line.push(if in_base { 'B' } else { '|' });
+        lines.push(line);
+    }
+    lines.push(border);
+    lines
+}
+
+/// Renders the placements of a player on one line.
+///
+/// Each placement shows its bound key, the symbol of the object,
+/// its cost, and the seconds left on its cooldown, if any.
+/// Placements the player cannot afford are enclosed in parentheses.
+/// The reason why the last action of the player was rejected, if it was,
+/// follows an exclamation mark at the end of the line.
+fn render_palette(game_controller: &Controller, player: Player) -> String {
+    let data = &game_controller.game().players()[player];
+    let key_binding = &game_controller.settings().key_binding[player];
+
+    let mut entries = Vec::new();
+    for (index, placement) in data.placements.iter().enumerate() {
+        let label = key_binding
+            .place
+            .get(index)
+            .map_or_else(|| "-".to_owned(), |&button| button_label(button));
+
+        let mut entry = format!("{}:{}", label, object_symbol(&placement.object, player));
+        for resource in Resource::ALL.iter().copied() {
+            let amount = placement.cost[resource];
+            if amount > 0 {
+                let _ = write!(entry, " {}{}", amount, resource_suffix(resource));
+            }
+        }
+
+        let cooldown = &placement.cooldown;
+        if !cooldown.is_over() {
+            let seconds = cooldown.remaining().div_ceil(TICKS_PER_SECOND);
+            let _ = write!(entry, " {seconds}s");
+        }
+
+        if data.resources.shortage(&placement.cost).is_some() {
+            entry = format!("({entry})");
+        }
+        entries.push(entry);
+    }
+    let mut line = entries.join("  ");
+    if let Some(error) = game_controller.action_error(player) {
+        let _ = write!(line, "  ! {error}");
+    }
+    line
+}
+
+/// Returns the symbol of an object,
+/// in upper case for the left player and in lower case for the right player.
+fn object_symbol(object: &Object, owner: Player) -> char {
+    let symbol = match object.kind {
+        Kind::Key { .. } => 'K',
+        Kind::Fire { .. } => 'F',
+        Kind::Barrier {} => '#',
+        Kind::Healer { .. } => '+',
+        Kind::Caster { .. } => '*',
+    };
+    match owner {
+        Player::Left => symbol,
+        Player::Right => symbol.to_ascii_lowercase(),
+    }
+}
+
+/// Returns a digit from `0` to `9` that shows the health of an object.
+fn health_digit(object: &Object) -> char {
+    let ratio = f64::from(object.health) / f64::from(object.max_health.max(1));
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let digit = (ratio * 9.0).ceil().clamp(0.0, 9.0) as u32;
+    std::char::from_digit(digit, 10).unwrap_or('?')
+}
+
+/// Returns the column of the cell a projectile is in,
+/// or `None` if it is off the board.
+fn column_of(column: f64) -> Option<usize> {
+    if column < 0.0 {
+        return None;
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let column = column.floor() as usize;
+    Some(column)
+}
+
+/// Returns the suffix that marks an amount of a resource.
+fn resource_suffix(resource: Resource) -> char {
+    match resource {
+        Resource::Keys => 'k',
+        Resource::Energy => 'e',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::{self, KeyBinding};
+    use crate::{Catalog, Players};
+    use piston::{Button, Key};
+
+    fn controller() -> Controller {
+        let key_binding = |place: Key| KeyBinding {
+            place: vec![Button::Keyboard(place)],
+            ..KeyBinding::unbound()
+        };
+
+        let game = Catalog::builtin().unwrap().game().unwrap();
+        let settings = controller::Settings {
+            key_binding: Players {
+                left: key_binding(Key::T),
+                right: key_binding(Key::D1),
+            },
+            ..controller::Settings::headless(game.settings())
+        };
+        Controller::new(settings, game).unwrap()
+    }
+
+    #[test]
+    fn renders_the_board_and_palettes() {
+        let mut controller = controller();
+        let lines = TextRenderer::new().render(&controller);
+
+        // the starter keys are selected at the start of the game
+        let row = &lines[8];
+        assert_eq!(&row[1..6], "[K9 ]");
+        assert_eq!(&row[row.len() - 6..row.len() - 1], "[k9 ]");
+
+        let palettes = &lines[lines.len() - 2..];
+        assert!(palettes[0].starts_with("T:"));
+        assert!(palettes[1].starts_with("1:"));
+
+        controller.set_paused(true);
+        let lines = TextRenderer::new().render(&controller);
+        assert!(lines[0].ends_with("PAUSED"));
+    }
+}
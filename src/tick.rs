@@ -0,0 +1,101 @@
+//! Fixed-timestep simulation.
+//!
+//! The game is simulated in ticks of fixed length,
+//! independent of the frame rate,
+//! and all durations in the game are expressed in ticks.
+//! A [`Scheduler`] turns the real time between frames
+//! into the number of ticks to be simulated.
+//!
+//! [`Scheduler`]: struct.Scheduler.html
+
+use std::time::Duration;
+
+/// The number of ticks per second of game time.
+pub const TICKS_PER_SECOND: u32 = 60;
+
+/// The length of one tick.
+#[allow(clippy::cast_lossless)]
+pub const TICK: Duration = Duration::from_nanos(1_000_000_000 / TICKS_PER_SECOND as u64);
+
+/// The maximum number of ticks simulated for a single frame.
+///
+/// Time beyond this limit is dropped,
+/// so that a long hitch slows the game down
+/// instead of freezing it while it catches up.
+pub const MAX_CATCH_UP_TICKS: u32 = TICKS_PER_SECOND / 4;
+
+/// A fixed-timestep scheduler.
+///
+/// The scheduler accumulates the real time passed between frames
+/// and hands it out in whole ticks,
+/// carrying the remainder over to the next frame.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Scheduler {
+    accumulator: Duration,
+}
+
+impl Scheduler {
+    /// Creates a new scheduler with no time accumulated.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accumulates the specified amount of real time,
+    /// and returns the number of ticks to be simulated,
+    /// up to [`MAX_CATCH_UP_TICKS`].
+    ///
+    /// [`MAX_CATCH_UP_TICKS`]: constant.MAX_CATCH_UP_TICKS.html
+    pub fn advance(&mut self, time: Duration) -> u32 {
+        self.accumulator += time;
+
+        let mut n_ticks = 0;
+        while self.accumulator >= TICK {
+            self.accumulator -= TICK;
+            n_ticks += 1;
+
+            if n_ticks == MAX_CATCH_UP_TICKS {
+                self.accumulator = self.accumulator.min(TICK);
+                break;
+            }
+        }
+        n_ticks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_ticks_carry_over() {
+        let mut scheduler = Scheduler::new();
+        assert_eq!(scheduler.advance(TICK / 2), 0);
+        assert_eq!(scheduler.advance(TICK / 2), 1);
+        assert_eq!(scheduler.advance(TICK + TICK / 2), 1);
+        assert_eq!(scheduler.advance(TICK / 2), 1);
+    }
+
+    #[test]
+    fn missed_ticks_are_caught_up() {
+        let mut scheduler = Scheduler::new();
+        assert_eq!(scheduler.advance(TICK * 5), 5);
+        assert_eq!(scheduler.advance(Duration::ZERO), 0);
+        assert_eq!(
+            scheduler.advance(TICK * MAX_CATCH_UP_TICKS),
+            MAX_CATCH_UP_TICKS
+        );
+        assert_eq!(scheduler.advance(Duration::ZERO), 0);
+    }
+
+    #[test]
+    fn long_hitches_are_dropped() {
+        let mut scheduler = Scheduler::new();
+        assert_eq!(
+            scheduler.advance(Duration::from_secs(10)),
+            MAX_CATCH_UP_TICKS
+        );
+        assert_eq!(scheduler.advance(Duration::ZERO), 1);
+        assert_eq!(scheduler.advance(Duration::ZERO), 0);
+        assert_eq!(scheduler.advance(TICK / 2), 0);
+    }
+}